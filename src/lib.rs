@@ -1,11 +1,32 @@
-#![feature(const_float_classify)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature has to be enabled for the float functions");
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 mod math;
 pub use math::{
+    affine2::Affine2,
+    approx::{ApproxEq, Tolerance},
+    circle::Circle,
+    error::Error,
+    float::Float,
     get_closest_point_on_line_segment, get_closest_point_on_line_segment_with_portion,
-    line2::{Line2, LineType},
+    get_closest_point_on_line_segment_with_portion_and_tolerance,
+    line2::{Line2, LineIntersection, LineType},
+    mat2::Mat2,
     point_line_segment_distance, point_line_segment_distance_squared,
+    predicates::{in_circle, orientation, CirclePosition, Orientation},
+    ray_cast::RayHit,
     rectangle::Rectangle,
-    trajectory::{LineSegmentIter, Node, StepRunner, TangentIter, Trajectory},
+    trajectory::{LineSegmentIter, Node, Step, StepRunner, Trajectory},
     vec2::Vec2,
 };
+#[cfg(feature = "alloc")]
+pub use math::{
+    convex_hull::convex_hull,
+    polygon::Polygon,
+    trajectory::{ArcLengthIndex, SegmentIndex, TangentIter, TrajectoryMut, VecTrajectory},
+};
@@ -1,49 +1,87 @@
+pub mod affine2;
+pub mod approx;
+pub mod circle;
+#[cfg(feature = "alloc")]
+pub mod convex_hull;
+pub mod error;
+pub mod float;
 pub mod line2;
+pub mod mat2;
+#[cfg(feature = "alloc")]
+pub mod polygon;
+pub mod predicates;
+pub mod ray_cast;
 pub mod rectangle;
 pub mod trajectory;
 pub mod vec2;
 
+use approx::Tolerance;
+use float::Float;
 use vec2::Vec2;
 
-pub fn get_closest_point_on_line_segment_with_portion(
-    line_start: Vec2,
-    line_end: Vec2,
-    point: Vec2,
-) -> (Vec2, f32) {
+pub fn get_closest_point_on_line_segment_with_portion<F: Float>(
+    line_start: Vec2<F>,
+    line_end: Vec2<F>,
+    point: Vec2<F>,
+) -> (Vec2<F>, F) {
+    get_closest_point_on_line_segment_with_portion_and_tolerance(
+        line_start,
+        line_end,
+        point,
+        &Tolerance::default(),
+    )
+}
+
+/// see get_closest_point_on_line_segment_with_portion
+/// the line segment is treated as a point if its squared length is smaller than tolerance.degenerate_length_squared
+pub fn get_closest_point_on_line_segment_with_portion_and_tolerance<F: Float>(
+    line_start: Vec2<F>,
+    line_end: Vec2<F>,
+    point: Vec2<F>,
+    tolerance: &Tolerance<F>,
+) -> (Vec2<F>, F) {
     //the following algorithm is from https://stackoverflow.com/questions/849211/shortest-euclidean_distance-between-a-point-and-a-line-segment
 
     let length_squared = line_end.euclidean_distance_squared(line_start);
 
-    if length_squared.abs() < 0.000001 {
+    if length_squared.abs() < tolerance.degenerate_length_squared {
         //if line_start == line_end
-        (line_start, 0.0)
+        (line_start, F::ZERO)
     } else {
         //line_start + (line_end - line_start) * t is our line segment
         // we calculate t so line_start + (line_end - line_start) * t is the closed point on the segment
         //therefore we clamp t from [0,1]
-        let t = f32::max(
-            0.0,
-            f32::min(
-                1.0,
-                ((point - line_start) * (line_end - line_start)) / length_squared,
-            ),
-        );
+        let t = (((point - line_start) * (line_end - line_start)) / length_squared)
+            .max(F::ZERO)
+            .min(F::ONE);
 
         (line_start + (line_end - line_start) * t, t)
     }
 }
 
-pub fn get_closest_point_on_line_segment(line_start: Vec2, line_end: Vec2, point: Vec2) -> Vec2 {
+pub fn get_closest_point_on_line_segment<F: Float>(
+    line_start: Vec2<F>,
+    line_end: Vec2<F>,
+    point: Vec2<F>,
+) -> Vec2<F> {
     get_closest_point_on_line_segment_with_portion(line_start, line_end, point).0
 }
 
-pub fn point_line_segment_distance(line_start: Vec2, line_end: Vec2, point: Vec2) -> f32 {
+pub fn point_line_segment_distance<F: Float>(
+    line_start: Vec2<F>,
+    line_end: Vec2<F>,
+    point: Vec2<F>,
+) -> F {
     let projection = get_closest_point_on_line_segment(line_start, line_end, point);
 
     point.euclidean_distance(projection)
 }
 
-pub fn point_line_segment_distance_squared(line_start: Vec2, line_end: Vec2, point: Vec2) -> f32 {
+pub fn point_line_segment_distance_squared<F: Float>(
+    line_start: Vec2<F>,
+    line_end: Vec2<F>,
+    point: Vec2<F>,
+) -> F {
     let projection = get_closest_point_on_line_segment(line_start, line_end, point);
 
     point.euclidean_distance_squared(projection)
@@ -54,7 +92,7 @@ macro_rules! max {
     ($x: expr) => ($x);
     ($x: expr, $($z: expr),+) => {{
         let y = max!($($z),*);
-        use std::cmp::Ordering;
+        use ::core::cmp::Ordering;
         match $x.partial_cmp(&y) {
             Some(Ordering::Less) => y,
             Some(Ordering::Equal) => $x,
@@ -71,7 +109,7 @@ macro_rules! min {
     ($x: expr) => ($x);
     ($x: expr, $($z: expr),+) => {{
         let y = min!($($z),*);
-        use std::cmp::Ordering;
+        use ::core::cmp::Ordering;
         match $x.partial_cmp(&y) {
             Some(Ordering::Less) => $x,
             Some(Ordering::Equal) => $x,
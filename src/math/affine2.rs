@@ -0,0 +1,163 @@
+use core::ops::Mul;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "alloc")]
+use super::trajectory::{Node, Trajectory, VecTrajectory};
+use super::{float::Float, line2::Line2, mat2::Mat2, rectangle::Rectangle, Vec2};
+
+/// an affine transformation: a linear transformation (rotation, scale, shear) followed by a translation
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Affine2<F: Float = f32> {
+    matrix: Mat2<F>,
+    translation: Vec2<F>,
+}
+
+impl<F: Float> Affine2<F> {
+    pub fn from_mat2_translation(matrix: Mat2<F>, translation: Vec2<F>) -> Affine2<F> {
+        Affine2 {
+            matrix,
+            translation,
+        }
+    }
+
+    pub fn identity() -> Affine2<F> {
+        Affine2::from_mat2(Mat2::identity())
+    }
+
+    pub fn from_mat2(matrix: Mat2<F>) -> Affine2<F> {
+        Affine2::from_mat2_translation(matrix, Vec2::new(F::ZERO, F::ZERO))
+    }
+
+    pub fn from_translation(translation: Vec2<F>) -> Affine2<F> {
+        Affine2::from_mat2_translation(Mat2::identity(), translation)
+    }
+
+    /// counter clockwise rotation around the origin, angle is in radians
+    pub fn from_angle(angle: F) -> Affine2<F> {
+        Affine2::from_mat2(Mat2::from_angle(angle))
+    }
+
+    pub fn from_scale(scale: Vec2<F>) -> Affine2<F> {
+        Affine2::from_mat2(Mat2::from_scale(scale))
+    }
+
+    /// see Mat2::from_shear
+    pub fn from_shear(shear_x: F, shear_y: F) -> Affine2<F> {
+        Affine2::from_mat2(Mat2::from_shear(shear_x, shear_y))
+    }
+
+    /// scales first, then rotates and then translates
+    pub fn from_scale_angle_translation(
+        scale: Vec2<F>,
+        angle: F,
+        translation: Vec2<F>,
+    ) -> Affine2<F> {
+        Affine2::from_mat2_translation(
+            Mat2::from_angle(angle) * Mat2::from_scale(scale),
+            translation,
+        )
+    }
+
+    /// converts the transformation into another precision
+    /// note: converting from f64 to f32 may lose precision
+    pub fn cast<G: Float>(self) -> Affine2<G> {
+        Affine2::from_mat2_translation(self.matrix.cast(), self.translation.cast())
+    }
+
+    pub fn matrix(&self) -> Mat2<F> {
+        self.matrix
+    }
+
+    pub fn translation(&self) -> Vec2<F> {
+        self.translation
+    }
+
+    /// returns None if the transformation is not invertible
+    pub fn inverse(&self) -> Option<Affine2<F>> {
+        let matrix = self.matrix.inverse()?;
+        Some(Affine2::from_mat2_translation(
+            matrix,
+            -(matrix * self.translation),
+        ))
+    }
+
+    /// first applies self and then other
+    pub fn then(self, other: Affine2<F>) -> Affine2<F> {
+        other * self
+    }
+
+    pub fn transform_point(&self, point: Vec2<F>) -> Vec2<F> {
+        self.matrix * point + self.translation
+    }
+
+    /// transforms a direction, the translation is ignored
+    pub fn transform_vector(&self, vector: Vec2<F>) -> Vec2<F> {
+        self.matrix * vector
+    }
+
+    /// the line type is kept, affine transformations keep the parameterization of lines
+    pub fn transform_line(&self, line: Line2<F>) -> Line2<F> {
+        Line2::from_start_and_end(
+            self.transform_point(line.start()),
+            self.transform_point(line.end()),
+            line.line_type(),
+        )
+    }
+
+    /// the bounding box of the transformed rectangle
+    pub fn transform_rectangle(&self, rectangle: Rectangle<F>) -> Rectangle<F> {
+        let mut bounding_box = Rectangle::new(
+            self.transform_point(rectangle.min()),
+            self.transform_point(rectangle.max()),
+        );
+        bounding_box
+            .extend_with_vec2(self.transform_point(Vec2::new(rectangle.left(), rectangle.top())));
+        bounding_box.extend_with_vec2(
+            self.transform_point(Vec2::new(rectangle.right(), rectangle.bottom())),
+        );
+        bounding_box
+    }
+
+    /// a closure that can be used as pos_converter for e.g. Trajectory::closest_point_to_pos
+    pub fn pos_converter(self) -> impl Fn(Vec2<F>) -> Vec2<F> {
+        move |pos| self.transform_point(pos)
+    }
+
+    /// transforms all node positions of the trajectory
+    /// node_builder gets the original node and its transformed position and has to create the new node
+    /// the new trajectory has the same id
+    #[cfg(feature = "alloc")]
+    pub fn transform_trajectory<T: Trajectory<F>, N: Node<F>>(
+        &self,
+        trajectory: &T,
+        mut node_builder: impl FnMut(&T::Node, Vec2<F>) -> N,
+    ) -> VecTrajectory<N> {
+        let nodes = trajectory
+            .nodes()
+            .iter()
+            .map(|node| node_builder(node, self.transform_point(node.pos())))
+            .collect();
+        VecTrajectory::from_nodes(trajectory.id(), nodes)
+    }
+}
+
+/// self * rhs first applies rhs and then self
+impl<F: Float> Mul for Affine2<F> {
+    type Output = Affine2<F>;
+    fn mul(self, rhs: Affine2<F>) -> Affine2<F> {
+        Affine2::from_mat2_translation(
+            self.matrix * rhs.matrix,
+            self.matrix * rhs.translation + self.translation,
+        )
+    }
+}
+
+impl<F: Float> Mul<Vec2<F>> for Affine2<F> {
+    type Output = Vec2<F>;
+    fn mul(self, rhs: Vec2<F>) -> Vec2<F> {
+        self.transform_point(rhs)
+    }
+}
@@ -0,0 +1,134 @@
+use super::{float::Float, line2::Line2, rectangle::Rectangle, Vec2};
+
+/// approximate equality for floats and the structs of this crate
+/// structs are approximately equal if all their components are
+pub trait ApproxEq<F = f32> {
+    /// |self - other| <= epsilon
+    fn abs_diff_eq(&self, other: &Self, epsilon: F) -> bool;
+
+    /// |self - other| <= epsilon or |self - other| <= max(|self|, |other|) * max_relative
+    fn relative_eq(&self, other: &Self, epsilon: F, max_relative: F) -> bool;
+
+    /// |self - other| <= epsilon or self and other are at most max_ulps representable floats apart
+    fn ulps_eq(&self, other: &Self, epsilon: F, max_ulps: u32) -> bool;
+}
+
+macro_rules! impl_approx_eq {
+    ($float: ident) => {
+        impl ApproxEq<$float> for $float {
+            fn abs_diff_eq(&self, other: &$float, epsilon: $float) -> bool {
+                //the equality check handles infinities
+                self == other || (self - other).abs() <= epsilon
+            }
+
+            fn relative_eq(&self, other: &$float, epsilon: $float, max_relative: $float) -> bool {
+                if self.abs_diff_eq(other, epsilon) {
+                    return true;
+                }
+                if !self.is_finite() || !other.is_finite() {
+                    return false;
+                }
+                let largest = self.abs().max(other.abs());
+                (self - other).abs() <= largest * max_relative
+            }
+
+            fn ulps_eq(&self, other: &$float, epsilon: $float, max_ulps: u32) -> bool {
+                if self.abs_diff_eq(other, epsilon) {
+                    return true;
+                }
+                if self.is_nan()
+                    || other.is_nan()
+                    || self.is_sign_positive() != other.is_sign_positive()
+                {
+                    return false;
+                }
+                //floats with the same sign are ordered like their bit patterns
+                self.to_bits().abs_diff(other.to_bits()) <= max_ulps.into()
+            }
+        }
+    };
+}
+
+impl_approx_eq!(f32);
+impl_approx_eq!(f64);
+
+impl<F: Float> ApproxEq<F> for Vec2<F> {
+    fn abs_diff_eq(&self, other: &Vec2<F>, epsilon: F) -> bool {
+        self.x().abs_diff_eq(&other.x(), epsilon) && self.y().abs_diff_eq(&other.y(), epsilon)
+    }
+
+    fn relative_eq(&self, other: &Vec2<F>, epsilon: F, max_relative: F) -> bool {
+        self.x().relative_eq(&other.x(), epsilon, max_relative)
+            && self.y().relative_eq(&other.y(), epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Vec2<F>, epsilon: F, max_ulps: u32) -> bool {
+        self.x().ulps_eq(&other.x(), epsilon, max_ulps)
+            && self.y().ulps_eq(&other.y(), epsilon, max_ulps)
+    }
+}
+
+impl<F: Float> ApproxEq<F> for Rectangle<F> {
+    fn abs_diff_eq(&self, other: &Rectangle<F>, epsilon: F) -> bool {
+        self.min().abs_diff_eq(&other.min(), epsilon)
+            && self.max().abs_diff_eq(&other.max(), epsilon)
+    }
+
+    fn relative_eq(&self, other: &Rectangle<F>, epsilon: F, max_relative: F) -> bool {
+        self.min().relative_eq(&other.min(), epsilon, max_relative)
+            && self.max().relative_eq(&other.max(), epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Rectangle<F>, epsilon: F, max_ulps: u32) -> bool {
+        self.min().ulps_eq(&other.min(), epsilon, max_ulps)
+            && self.max().ulps_eq(&other.max(), epsilon, max_ulps)
+    }
+}
+
+/// the line types have to be equal
+impl<F: Float> ApproxEq<F> for Line2<F> {
+    fn abs_diff_eq(&self, other: &Line2<F>, epsilon: F) -> bool {
+        self.line_type() == other.line_type()
+            && self.start().abs_diff_eq(&other.start(), epsilon)
+            && self.end().abs_diff_eq(&other.end(), epsilon)
+    }
+
+    fn relative_eq(&self, other: &Line2<F>, epsilon: F, max_relative: F) -> bool {
+        self.line_type() == other.line_type()
+            && self
+                .start()
+                .relative_eq(&other.start(), epsilon, max_relative)
+            && self.end().relative_eq(&other.end(), epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Line2<F>, epsilon: F, max_ulps: u32) -> bool {
+        self.line_type() == other.line_type()
+            && self.start().ulps_eq(&other.start(), epsilon, max_ulps)
+            && self.end().ulps_eq(&other.end(), epsilon, max_ulps)
+    }
+}
+
+/// the tolerances used by the numerical algorithms of this crate
+/// the default values are the ones used by the functions without a tolerance parameter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance<F: Float = f32> {
+    ///line segments with a smaller squared length are treated as a single point when projecting onto them
+    pub degenerate_length_squared: F,
+    ///lines are treated as parallel if the determinant of their directions is not bigger than this
+    pub parallel_determinant: F,
+    ///the maximal distance between the intersection points calculated on both lines
+    pub intersection_self_check: F,
+    ///how far the portion of a step may exceed 1.0 due to rounding errors
+    pub step_overshoot: F,
+}
+
+impl<F: Float> Default for Tolerance<F> {
+    fn default() -> Tolerance<F> {
+        Tolerance {
+            degenerate_length_squared: F::from_f32(0.000001),
+            parallel_determinant: F::from_f32(0.001),
+            intersection_self_check: F::ONE,
+            step_overshoot: F::from_f32(0.001),
+        }
+    }
+}
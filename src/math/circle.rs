@@ -0,0 +1,198 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "alloc")]
+use super::ray_cast::sorted_hits;
+use super::{
+    float::Float,
+    line2::Line2,
+    ray_cast::{facing_normal, first_hit, RayHit},
+    rectangle::Rectangle,
+    Vec2,
+};
+
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Circle<F: Float = f32> {
+    center: Vec2<F>,
+    radius: F,
+}
+
+impl<F: Float> Circle<F> {
+    /// a negative radius is converted to its absolute value
+    pub fn new(center: Vec2<F>, radius: F) -> Circle<F> {
+        Circle {
+            center,
+            radius: radius.abs(),
+        }
+    }
+
+    /// converts the circle into another precision
+    /// note: converting from f64 to f32 may lose precision
+    pub fn cast<G: Float>(self) -> Circle<G> {
+        Circle {
+            center: self.center.cast(),
+            radius: G::from_f64(self.radius.to_f64()),
+        }
+    }
+
+    pub fn center(&self) -> Vec2<F> {
+        self.center
+    }
+
+    pub fn radius(&self) -> F {
+        self.radius
+    }
+
+    pub fn area(&self) -> F {
+        F::PI * self.radius * self.radius
+    }
+
+    pub fn circumference(&self) -> F {
+        F::TWO * F::PI * self.radius
+    }
+
+    pub fn bounding_rectangle(&self) -> Rectangle<F> {
+        Rectangle::from_center_and_size(self.center, Vec2::new(self.radius, self.radius) * F::TWO)
+    }
+
+    /// true if pos is inside the circle or on its border
+    pub fn contains(&self, pos: Vec2<F>) -> bool {
+        self.center.euclidean_distance_squared(pos) <= self.radius * self.radius
+    }
+
+    /// the points where the border of the circle intersects the line
+    /// respects the line type of the line
+    /// the points are ordered along the line from start to end
+    #[cfg(feature = "alloc")]
+    pub fn intersections_with_line(&self, line: Line2<F>) -> Vec<Vec2<F>> {
+        let delta = line.end() - line.start();
+        self.intersection_parameters(line)
+            .map(|p| line.start() + delta * p)
+            .collect()
+    }
+
+    /// the ascending parameters p of the points start + (end - start) * p where line intersects the border
+    /// respects the line type of the line
+    fn intersection_parameters(&self, line: Line2<F>) -> impl Iterator<Item = F> {
+        //we solve |start + delta * p - center|^2 = radius^2 for p
+        let delta = line.end() - line.start();
+        let offset = line.start() - self.center;
+
+        let a = delta * delta;
+        let b = F::TWO * (offset * delta);
+        let c = offset * offset - self.radius * self.radius;
+
+        let discriminant = b * b - F::TWO * F::TWO * a * c;
+        let (parameters, number_of_parameters) = if a == F::ZERO || discriminant < F::ZERO {
+            //the line has no direction or misses the circle
+            ([F::ZERO; 2], 0)
+        } else if discriminant == F::ZERO {
+            ([-b / (F::TWO * a), F::ZERO], 1)
+        } else {
+            let root = discriminant.sqrt();
+            ([(-b - root) / (F::TWO * a), (-b + root) / (F::TWO * a)], 2)
+        };
+
+        parameters
+            .into_iter()
+            .take(number_of_parameters)
+            .filter(move |&p| line.line_type().contains_parameter(p))
+    }
+
+    /// the hits of ray on the border
+    fn ray_hits(&self, ray: Line2<F>) -> impl Iterator<Item = RayHit<F>> + '_ {
+        let delta = ray.end() - ray.start();
+        self.intersection_parameters(ray).map(move |p| {
+            let pos = ray.start() + delta * p;
+            let radius_vector = pos - self.center;
+            let angle = radius_vector.y().atan2(radius_vector.x());
+            let portion = if angle < F::ZERO {
+                angle / (F::TWO * F::PI) + F::ONE
+            } else {
+                angle / (F::TWO * F::PI)
+            };
+            //the tangent is orthogonal to the radius vector
+            let tangent = Vec2::new(-radius_vector.y(), radius_vector.x());
+            RayHit::new(pos, p, 0, portion, facing_normal(tangent, delta))
+        })
+    }
+
+    /// the first hit of ray on the border, the segment index is always 0
+    pub fn ray_cast(&self, ray: Line2<F>) -> Option<RayHit<F>> {
+        first_hit(self.ray_hits(ray))
+    }
+
+    /// all hits of ray on the border sorted by the ray parameter, see ray_cast
+    #[cfg(feature = "alloc")]
+    pub fn ray_cast_all(&self, ray: Line2<F>) -> Vec<RayHit<F>> {
+        sorted_hits(self.ray_hits(ray))
+    }
+
+    /// the points where the borders of both circles intersect
+    /// returns no points if the circles are identical
+    #[cfg(feature = "alloc")]
+    pub fn intersections_with_circle(&self, other: Circle<F>) -> Vec<Vec2<F>> {
+        //the following algorithm is from https://paulbourke.net/geometry/circlesphere/
+        let delta = other.center - self.center;
+        let distance = delta.euclidean_lenght();
+
+        if distance == F::ZERO
+            || distance > self.radius + other.radius
+            || distance < (self.radius - other.radius).abs()
+        {
+            return Vec::new();
+        }
+
+        //distance from self.center to the chord between the intersection points
+        let a = (self.radius * self.radius - other.radius * other.radius + distance * distance)
+            / (F::TWO * distance);
+        let h_squared = self.radius * self.radius - a * a;
+        let chord_center = self.center + delta * (a / distance);
+
+        if h_squared <= F::ZERO {
+            //the circles touch
+            vec![chord_center]
+        } else {
+            let h = h_squared.sqrt();
+            let offset = Vec2::new(-delta.y(), delta.x()) * (h / distance);
+            vec![chord_center + offset, chord_center - offset]
+        }
+    }
+
+    /// true if the circles share at least one point
+    pub fn overlaps_circle(&self, other: Circle<F>) -> bool {
+        let radii = self.radius + other.radius;
+        self.center.euclidean_distance_squared(other.center) <= radii * radii
+    }
+
+    /// true if the circle and the rectangle share at least one point
+    pub fn overlaps_rectangle(&self, rectangle: Rectangle<F>) -> bool {
+        let closest_point = Vec2::new(
+            self.center.x().max(rectangle.left()).min(rectangle.right()),
+            self.center.y().max(rectangle.bottom()).min(rectangle.top()),
+        );
+        self.contains(closest_point)
+    }
+
+    /// true if the rectangle is completely inside the circle
+    pub fn contains_rectangle(&self, rectangle: Rectangle<F>) -> bool {
+        self.contains(rectangle.min())
+            && self.contains(rectangle.max())
+            && self.contains(Vec2::new(rectangle.left(), rectangle.top()))
+            && self.contains(Vec2::new(rectangle.right(), rectangle.bottom()))
+    }
+
+    /// true if the circle is completely inside the rectangle
+    pub fn is_inside_rectangle(&self, rectangle: Rectangle<F>) -> bool {
+        rectangle.shrink(self.radius).contains(self.center)
+    }
+}
+
+impl From<Circle<f32>> for Circle<f64> {
+    fn from(circle: Circle<f32>) -> Circle<f64> {
+        circle.cast()
+    }
+}
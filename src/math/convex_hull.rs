@@ -0,0 +1,59 @@
+use core::cmp::Ordering;
+
+use alloc::vec::Vec;
+
+use super::{
+    float::Float,
+    polygon::Polygon,
+    predicates::{orientation, Orientation},
+    Vec2,
+};
+
+/// true if the last two points of the hull and point do not form a counter clockwise turn
+fn is_not_left_turn<F: Float>(hull: &[Vec2<F>], point: Vec2<F>) -> bool {
+    orientation(hull[hull.len() - 2], hull[hull.len() - 1], point) != Orientation::CounterClockwise
+}
+
+/// computes the convex hull with Andrew's monotone chain algorithm
+/// the hull is counter clockwise and starts at the point with the smallest x (and then smallest y) coordinate
+/// duplicate points and points on the edges of the hull are not part of the result
+/// if all points are collinear the hull consists of the two extreme points
+/// points that are not finite are ignored
+pub fn convex_hull<F: Float>(points: impl IntoIterator<Item = Vec2<F>>) -> Polygon<F> {
+    let mut points: Vec<Vec2<F>> = points.into_iter().filter(|p| p.is_finite()).collect();
+    points.sort_by(|a, b| {
+        a.x()
+            .partial_cmp(&b.x())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.y().partial_cmp(&b.y()).unwrap_or(Ordering::Equal))
+    });
+    points.dedup();
+
+    if points.len() < 3 {
+        return Polygon::new(points);
+    }
+
+    let mut hull: Vec<Vec2<F>> = Vec::with_capacity(points.len() + 1);
+
+    //lower hull
+    for &point in &points {
+        while hull.len() >= 2 && is_not_left_turn(&hull, point) {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+
+    //upper hull
+    let lower_len = hull.len() + 1;
+    for &point in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len && is_not_left_turn(&hull, point) {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+
+    //the last point is the first point again
+    hull.pop();
+
+    Polygon::new(hull)
+}
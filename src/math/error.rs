@@ -0,0 +1,31 @@
+use core::fmt::{self, Display};
+
+/// the error type of all fallible functions of this crate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    ///the start of a range is after its end
+    InvalidRange { start: usize, end: usize },
+    ///an index is not smaller than the length of the indexed sequence
+    IndexOutOfBounds { index: usize, len: usize },
+    ///a step length has to be positive and finite
+    InvalidStepLength,
+    ///a numerical self check failed, so the result can not be trusted
+    NumericalInstability,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidRange { start, end } => {
+                write!(f, "invalid range: start {start} is after end {end}")
+            }
+            Error::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds for length {len}")
+            }
+            Error::InvalidStepLength => write!(f, "the step length has to be positive and finite"),
+            Error::NumericalInstability => write!(f, "the numerical self check failed"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
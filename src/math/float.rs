@@ -0,0 +1,160 @@
+use core::{
+    fmt::{Debug, Display},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use super::approx::ApproxEq;
+
+/// The scalar type used by all structs of this crate.
+/// Implemented for f32 (the default everywhere) and f64.
+pub trait Float:
+    'static
+    + Copy
+    + Debug
+    + Display
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + ApproxEq<Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+    const EPSILON: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+    const NAN: Self;
+    const MAX: Self;
+    const MIN: Self;
+    const PI: Self;
+
+    fn from_f32(value: f32) -> Self;
+
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f32(self) -> f32;
+
+    fn to_f64(self) -> f64;
+
+    fn sqrt(self) -> Self;
+
+    fn abs(self) -> Self;
+
+    fn min(self, other: Self) -> Self;
+
+    fn max(self, other: Self) -> Self;
+
+    fn is_finite(self) -> bool;
+
+    fn is_nan(self) -> bool;
+
+    fn sin(self) -> Self;
+
+    fn cos(self) -> Self;
+
+    fn acos(self) -> Self;
+
+    fn atan2(self, other: Self) -> Self;
+}
+
+/// uses the std implementation if available and the libm one otherwise
+#[cfg(feature = "std")]
+macro_rules! std_or_libm {
+    ($std: path, $libm: path) => {
+        $std
+    };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! std_or_libm {
+    ($std: path, $libm: path) => {
+        $libm
+    };
+}
+
+macro_rules! impl_float {
+    ($float: ident, $sqrt: ident, $sin: ident, $cos: ident, $acos: ident, $atan2: ident) => {
+        impl Float for $float {
+            const ZERO: $float = 0.0;
+            const ONE: $float = 1.0;
+            const TWO: $float = 2.0;
+            const EPSILON: $float = $float::EPSILON;
+            const INFINITY: $float = $float::INFINITY;
+            const NEG_INFINITY: $float = $float::NEG_INFINITY;
+            const NAN: $float = $float::NAN;
+            const MAX: $float = $float::MAX;
+            const MIN: $float = $float::MIN;
+            const PI: $float = core::$float::consts::PI;
+
+            fn from_f32(value: f32) -> $float {
+                value as $float
+            }
+
+            fn from_f64(value: f64) -> $float {
+                value as $float
+            }
+
+            fn to_f32(self) -> f32 {
+                self as f32
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn sqrt(self) -> $float {
+                std_or_libm!($float::sqrt, libm::$sqrt)(self)
+            }
+
+            fn abs(self) -> $float {
+                $float::abs(self)
+            }
+
+            fn min(self, other: $float) -> $float {
+                $float::min(self, other)
+            }
+
+            fn max(self, other: $float) -> $float {
+                $float::max(self, other)
+            }
+
+            fn is_finite(self) -> bool {
+                $float::is_finite(self)
+            }
+
+            fn is_nan(self) -> bool {
+                $float::is_nan(self)
+            }
+
+            fn sin(self) -> $float {
+                std_or_libm!($float::sin, libm::$sin)(self)
+            }
+
+            fn cos(self) -> $float {
+                std_or_libm!($float::cos, libm::$cos)(self)
+            }
+
+            fn acos(self) -> $float {
+                std_or_libm!($float::acos, libm::$acos)(self)
+            }
+
+            fn atan2(self, other: $float) -> $float {
+                std_or_libm!($float::atan2, libm::$atan2)(self, other)
+            }
+        }
+    };
+}
+
+impl_float!(f32, sqrtf, sinf, cosf, acosf, atan2f);
+impl_float!(f64, sqrt, sin, cos, acos, atan2);
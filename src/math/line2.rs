@@ -1,6 +1,13 @@
-use super::Vec2;
+use super::{
+    approx::Tolerance,
+    error::Error,
+    float::Float,
+    predicates::{cross_of_differences, orientation, Orientation},
+    rectangle::Rectangle,
+    Vec2,
+};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LineType {
     ///Gerade
     Line,
@@ -9,23 +16,121 @@ pub enum LineType {
     ///Strecke
     LineSegment,
 }
-#[derive(Clone, Copy, Debug)]
-pub struct Line2 {
-    start: Vec2,
-    end: Vec2,
+
+impl LineType {
+    /// checks if start + (end - start) * parameter is part of a line of this type
+    pub(crate) fn contains_parameter<F: Float>(self, parameter: F) -> bool {
+        use LineType::{Line, LineSegment, Ray};
+        match self {
+            Line => true,
+            Ray => parameter >= F::ZERO,
+            LineSegment => (F::ZERO..=F::ONE).contains(&parameter),
+        }
+    }
+
+    /// checks if the parameter numerator / denominator is contained, denominator must not be 0.0
+    /// rest has to have the sign of denominator - numerator
+    /// exact if the signs of the arguments are exact
+    fn contains_fraction(self, numerator: f64, rest: f64, denominator: f64) -> bool {
+        let not_negative = |x: f64| x == 0.0 || (x > 0.0) == (denominator > 0.0);
+        use LineType::{Line, LineSegment, Ray};
+        match self {
+            Line => true,
+            Ray => not_negative(numerator),
+            LineSegment => not_negative(numerator) && not_negative(rest),
+        }
+    }
+
+    fn clamp_parameter<F: Float>(self, parameter: F) -> F {
+        use LineType::{Line, LineSegment, Ray};
+        match self {
+            Line => parameter,
+            Ray => parameter.max(F::ZERO),
+            LineSegment => parameter.max(F::ZERO).min(F::ONE),
+        }
+    }
+
+    fn number_of_end_points(self) -> usize {
+        use LineType::{Line, LineSegment, Ray};
+        match self {
+            Line => 0,
+            Ray => 1,
+            LineSegment => 2,
+        }
+    }
+
+    /// the covered interval of a line from start to end, projected onto an axis
+    fn interval(self, start: f64, end: f64) -> (f64, f64) {
+        use LineType::{Line, LineSegment, Ray};
+        match self {
+            Line => (f64::NEG_INFINITY, f64::INFINITY),
+            Ray if start < end => (start, f64::INFINITY),
+            Ray => (f64::NEG_INFINITY, start),
+            LineSegment => (start.min(end), start.max(end)),
+        }
+    }
+}
+
+/// the exact relation of two lines, the parameters are approximated
+enum Relation {
+    Disjoint,
+    ///the parameters of the intersection point on self and other
+    Point(f64, f64),
+    ///the lines are collinear and share more than one point
+    ///the shared part covers [min, max] on the axis returned by axis_coordinate for self
+    Overlap {
+        min: f64,
+        max: f64,
+    },
+}
+
+/// the coordinate of the axis along which start and end differ
+fn axis_coordinate(start: Vec2<f64>, end: Vec2<f64>, pos: Vec2<f64>) -> f64 {
+    if start.x() != end.x() {
+        pos.x()
+    } else {
+        pos.y()
+    }
+}
+
+/// the result of Line2::intersection_with_overlap
+/// the parameters p describe the points start + (end - start) * p on the respective line
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineIntersection<F: Float = f32> {
+    None,
+    Point {
+        pos: Vec2<F>,
+        self_parameter: F,
+        other_parameter: F,
+    },
+    /// both lines are collinear and share more than one point
+    /// overlap is a Line, Ray or LineSegment depending on the shape of the shared part
+    /// lines and line segments point in the direction of self, rays may point against it
+    /// the parameters are the ones of overlap.start() and overlap.end()
+    Overlap {
+        overlap: Line2<F>,
+        self_parameters: (F, F),
+        other_parameters: (F, F),
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Line2<F: Float = f32> {
+    start: Vec2<F>,
+    end: Vec2<F>,
     line_type: LineType,
 }
 
-impl Line2 {
+impl<F: Float> Line2<F> {
     pub fn from_support_point_and_vector(
-        support_point: Vec2,
-        vector: Vec2,
+        support_point: Vec2<F>,
+        vector: Vec2<F>,
         line_type: LineType,
-    ) -> Line2 {
+    ) -> Line2<F> {
         Line2::from_start_and_end(support_point, support_point + vector, line_type)
     }
 
-    pub fn from_start_and_end(start: Vec2, end: Vec2, line_type: LineType) -> Line2 {
+    pub fn from_start_and_end(start: Vec2<F>, end: Vec2<F>, line_type: LineType) -> Line2<F> {
         Line2 {
             start,
             end,
@@ -33,11 +138,21 @@ impl Line2 {
         }
     }
 
-    pub fn start(&self) -> Vec2 {
+    /// converts the line into another precision
+    /// note: converting from f64 to f32 may lose precision
+    pub fn cast<G: Float>(self) -> Line2<G> {
+        Line2 {
+            start: self.start.cast(),
+            end: self.end.cast(),
+            line_type: self.line_type,
+        }
+    }
+
+    pub fn start(&self) -> Vec2<F> {
         self.start
     }
 
-    pub fn end(&self) -> Vec2 {
+    pub fn end(&self) -> Vec2<F> {
         self.end
     }
 
@@ -46,8 +161,36 @@ impl Line2 {
     }
 }
 
-impl Line2 {
-    pub fn intersection(&self, other: Line2) -> Option<Vec2> {
+impl<F: Float> Line2<F> {
+    /// the intersection point of both lines, respecting their line types
+    /// returns None if there is no intersection, the lines are parallel or the numerical self check fails
+    pub fn intersection(&self, other: Line2<F>) -> Option<Vec2<F>> {
+        self.try_intersection(other).ok().flatten()
+    }
+
+    /// same as intersection but returns Error::NumericalInstability if the
+    /// intersection points calculated on both lines are 1.0 or more apart
+    pub fn try_intersection(&self, other: Line2<F>) -> Result<Option<Vec2<F>>, Error> {
+        self.try_intersection_with_tolerance(other, &Tolerance::default())
+    }
+
+    /// same as intersection with configurable tolerances, see Tolerance
+    pub fn intersection_with_tolerance(
+        &self,
+        other: Line2<F>,
+        tolerance: &Tolerance<F>,
+    ) -> Option<Vec2<F>> {
+        self.try_intersection_with_tolerance(other, tolerance)
+            .ok()
+            .flatten()
+    }
+
+    /// same as try_intersection with configurable tolerances, see Tolerance
+    pub fn try_intersection_with_tolerance(
+        &self,
+        other: Line2<F>,
+        tolerance: &Tolerance<F>,
+    ) -> Result<Option<Vec2<F>>, Error> {
         //the following algorithm is from https://stackoverflow.com/questions/385305/efficient-maths-algorithm-to-calculate-intersections
         let s1 = self.start;
         let e1 = self.end;
@@ -59,7 +202,7 @@ impl Line2 {
         let d2 = e2 - s2;
 
         let determinante = d1.x() * d2.y() - d1.y() * d2.x();
-        if determinante.abs() > 0.001 {
+        if determinante.abs() > tolerance.parallel_determinant {
             //delta Start Points
             let ds = s1 - s2;
 
@@ -69,32 +212,396 @@ impl Line2 {
             //intersection pos for other
             let p2 = (ds.y() * d1.x() - ds.x() * d1.y()) / determinante;
 
-            if ((s2 + d2 * p2) - (s1 + d1 * p1)).euclidean_lenght() >= 1.0 {
-                dbg!(((s2 + d2 * p2) - (s1 + d1 * p1)).euclidean_lenght());
-                assert!(((s2 + d2 * p2) - (s1 + d1 * p1)).euclidean_lenght() < 1.0);
+            if ((s2 + d2 * p2) - (s1 + d1 * p1)).euclidean_lenght()
+                >= tolerance.intersection_self_check
+            {
+                return Err(Error::NumericalInstability);
             }
 
-            use LineType::{Line, LineSegment, Ray};
-            let intersection_on_self = match self.line_type {
-                Line => true,
-                Ray => p1 >= 0.0,
-                LineSegment => (0.0..=1.0).contains(&p1),
-            };
-
-            let intersection_on_other = match other.line_type {
-                Line => true,
-                Ray => p2 >= 0.0,
-                LineSegment => (0.0..=1.0).contains(&p2),
-            };
+            let intersection_on_self = self.line_type.contains_parameter(p1);
+            let intersection_on_other = other.line_type.contains_parameter(p2);
 
             if intersection_on_self && intersection_on_other {
-                Some(s1 + d1 * p1)
+                Ok(Some(s1 + d1 * p1))
             } else {
-                None
+                Ok(None)
             }
         } else {
             //lines are parallel
-            None
+            Ok(None)
+        }
+    }
+}
+
+impl<F: Float> Line2<F> {
+    /// exact check if both lines have a common point, respecting their line types
+    /// in contrast to intersection this is also true for collinear overlapping lines
+    pub fn intersects(&self, other: Line2<F>) -> bool {
+        !matches!(self.relation(&other), Relation::Disjoint)
+    }
+
+    /// the intersection point of both lines, respecting their line types
+    /// whether the lines intersect is decided exactly, only the point itself is approximated
+    /// returns None if there is no intersection or the lines are collinear and share more than one point
+    pub fn robust_intersection(&self, other: Line2<F>) -> Option<Vec2<F>> {
+        match self.intersection_with_overlap(other) {
+            LineIntersection::Point { pos, .. } => Some(pos),
+            LineIntersection::None | LineIntersection::Overlap { .. } => None,
+        }
+    }
+
+    /// the complete intersection of both lines, respecting their line types
+    /// in contrast to intersection collinear lines return their shared part
+    /// whether and how the lines intersect is decided exactly, the positions and parameters are approximated
+    pub fn intersection_with_overlap(&self, other: Line2<F>) -> LineIntersection<F> {
+        let start = self.start.cast::<f64>();
+        let direction = self.end.cast::<f64>() - start;
+        match self.relation(&other) {
+            Relation::Disjoint => LineIntersection::None,
+            Relation::Point(self_parameter, other_parameter) => LineIntersection::Point {
+                pos: if self_parameter == 1.0 {
+                    self.end
+                } else {
+                    (start + direction * self_parameter).cast()
+                },
+                self_parameter: F::from_f64(self_parameter),
+                other_parameter: F::from_f64(other_parameter),
+            },
+            Relation::Overlap { min, max } => {
+                //the shared part in the direction of self
+                let axis_direction = axis_coordinate(start, start + direction, direction);
+                let (first, last) = if axis_direction > 0.0 {
+                    (min, max)
+                } else {
+                    (max, min)
+                };
+
+                let (overlap_start, overlap_end, line_type) =
+                    match (first.is_finite(), last.is_finite()) {
+                        (true, true) => (
+                            self.point_at_axis_coordinate(&other, first),
+                            self.point_at_axis_coordinate(&other, last),
+                            LineType::LineSegment,
+                        ),
+                        (true, false) => {
+                            let ray_start = self.point_at_axis_coordinate(&other, first);
+                            (ray_start, ray_start + direction, LineType::Ray)
+                        }
+                        (false, true) => {
+                            //a ray against the direction of self
+                            let ray_start = self.point_at_axis_coordinate(&other, last);
+                            (ray_start, ray_start - direction, LineType::Ray)
+                        }
+                        (false, false) => (start, start + direction, LineType::Line),
+                    };
+
+                let parameters = |line: &Line2<F>| {
+                    let line_start = line.start.cast::<f64>();
+                    let line_end = line.end.cast::<f64>();
+                    let parameter = |pos| {
+                        let axis = |pos| axis_coordinate(start, start + direction, pos);
+                        F::from_f64(
+                            (axis(pos) - axis(line_start)) / (axis(line_end) - axis(line_start)),
+                        )
+                    };
+                    (parameter(overlap_start), parameter(overlap_end))
+                };
+
+                LineIntersection::Overlap {
+                    overlap: Line2::from_start_and_end(
+                        overlap_start.cast(),
+                        overlap_end.cast(),
+                        line_type,
+                    ),
+                    self_parameters: parameters(self),
+                    other_parameters: parameters(&other),
+                }
+            }
+        }
+    }
+
+    /// the parameter of pos on self, if pos is part of self
+    /// the check is exact, the parameter is approximated
+    fn exact_parameter_of(&self, pos: Vec2<f64>) -> Option<f64> {
+        let start = self.start.cast::<f64>();
+        let end = self.end.cast::<f64>();
+        if start == end {
+            return (pos == start).then_some(0.0);
+        }
+        if cross_of_differences(pos, start, end, start) != 0.0 {
+            return None;
+        }
+
+        //pos is on the line, so its position along it can be compared on one axis
+        let (start, pos, end) = (
+            axis_coordinate(start, end, start),
+            axis_coordinate(start, end, pos),
+            axis_coordinate(start, end, end),
+        );
+        let (min, max) = self.line_type.interval(start, end);
+        (min <= pos && pos <= max).then(|| (pos - start) / (end - start))
+    }
+
+    /// the end point of self or other with the given axis coordinate
+    /// only valid for collinear lines and coordinates of end points
+    fn point_at_axis_coordinate(&self, other: &Line2<F>, coordinate: f64) -> Vec2<f64> {
+        let start = self.start.cast::<f64>();
+        let end = self.end.cast::<f64>();
+        [start, end, other.start.cast(), other.end.cast()]
+            .into_iter()
+            .find(|&pos| axis_coordinate(start, end, pos) == coordinate)
+            .unwrap_or(start)
+    }
+
+    fn relation(&self, other: &Line2<F>) -> Relation {
+        let s1 = self.start.cast::<f64>();
+        let e1 = self.end.cast::<f64>();
+        let s2 = other.start.cast::<f64>();
+        let e2 = other.end.cast::<f64>();
+
+        //lines without direction are single points
+        if s2 == e2 {
+            return match self.exact_parameter_of(s2) {
+                Some(parameter) => Relation::Point(parameter, 0.0),
+                None => Relation::Disjoint,
+            };
+        }
+        if s1 == e1 {
+            return match other.exact_parameter_of(s1) {
+                Some(parameter) => Relation::Point(0.0, parameter),
+                None => Relation::Disjoint,
+            };
+        }
+
+        //all values have exact signs
+        //the intersection is at s1 + (e1 - s1) * numerator_1 / denominator
+        //and at s2 + (e2 - s2) * numerator_2 / denominator
+        let denominator = cross_of_differences(e1, s1, e2, s2);
+        let numerator_2 = cross_of_differences(s2, s1, e1, s1);
+
+        if denominator != 0.0 {
+            let numerator_1 = cross_of_differences(s2, s1, e2, s2);
+            let rest_1 = cross_of_differences(e1, s2, e2, s2);
+            let rest_2 = cross_of_differences(e1, s1, e2, s1);
+            if self
+                .line_type
+                .contains_fraction(numerator_1, rest_1, denominator)
+                && other
+                    .line_type
+                    .contains_fraction(numerator_2, rest_2, denominator)
+            {
+                //intersections at the end points get their exact parameters
+                let parameter = |line_type: LineType, numerator: f64, rest: f64| {
+                    if numerator == 0.0 {
+                        0.0
+                    } else if rest == 0.0 {
+                        1.0
+                    } else {
+                        line_type.clamp_parameter(numerator / denominator)
+                    }
+                };
+                Relation::Point(
+                    parameter(self.line_type, numerator_1, rest_1),
+                    parameter(other.line_type, numerator_2, rest_2),
+                )
+            } else {
+                Relation::Disjoint
+            }
+        } else if numerator_2 != 0.0 {
+            //parallel but not on the same line
+            Relation::Disjoint
+        } else {
+            let (min_1, max_1) = self
+                .line_type
+                .interval(axis_coordinate(s1, e1, s1), axis_coordinate(s1, e1, e1));
+            let (min_2, max_2) = other
+                .line_type
+                .interval(axis_coordinate(s1, e1, s2), axis_coordinate(s1, e1, e2));
+            let (min, max) = (min_1.max(min_2), max_1.min(max_2));
+            if min < max {
+                Relation::Overlap { min, max }
+            } else if min == max {
+                //the lines only touch at an end point
+                let pos = self.point_at_axis_coordinate(other, min);
+                let parameter = |start: Vec2<f64>, end: Vec2<f64>| {
+                    let axis = |pos| axis_coordinate(s1, e1, pos);
+                    (axis(pos) - axis(start)) / (axis(end) - axis(start))
+                };
+                Relation::Point(parameter(s1, e1), parameter(s2, e2))
+            } else {
+                Relation::Disjoint
+            }
+        }
+    }
+}
+
+impl<F: Float> Line2<F> {
+    /// the closest point on self to pos and its parameter, respecting the line type
+    /// lines shorter than Tolerance::default().degenerate_length_squared are treated as their start point
+    pub fn closest_point_with_parameter(&self, pos: Vec2<F>) -> (Vec2<F>, F) {
+        self.closest_point_with_parameter_and_tolerance(pos, &Tolerance::default())
+    }
+
+    /// same as closest_point_with_parameter with configurable tolerances, see Tolerance
+    /// lines with a squared length smaller than tolerance.degenerate_length_squared are treated as their start point
+    pub fn closest_point_with_parameter_and_tolerance(
+        &self,
+        pos: Vec2<F>,
+        tolerance: &Tolerance<F>,
+    ) -> (Vec2<F>, F) {
+        let direction = self.end - self.start;
+        let length_squared = direction * direction;
+        if length_squared < tolerance.degenerate_length_squared {
+            return (self.start, F::ZERO);
+        }
+
+        let parameter = self
+            .line_type
+            .clamp_parameter(((pos - self.start) * direction) / length_squared);
+        (self.start + direction * parameter, parameter)
+    }
+
+    pub fn closest_point(&self, pos: Vec2<F>) -> Vec2<F> {
+        self.closest_point_with_parameter(pos).0
+    }
+
+    pub fn distance_to_point(&self, pos: Vec2<F>) -> F {
+        pos.euclidean_distance(self.closest_point(pos))
+    }
+
+    /// same as distance_to_point, but negative if pos is right of the direction from start to end
+    /// the side is decided exactly
+    pub fn signed_distance(&self, pos: Vec2<F>) -> F {
+        let distance = self.distance_to_point(pos);
+        match orientation(self.start, self.end, pos) {
+            Orientation::Clockwise => -distance,
+            Orientation::CounterClockwise | Orientation::Collinear => distance,
         }
     }
+
+    /// the closest points on self and on other, respecting their line types
+    /// both points are the same if the lines intersect
+    pub fn closest_points(&self, other: Line2<F>) -> (Vec2<F>, Vec2<F>) {
+        self.closest_points_with_tolerance(other, &Tolerance::default())
+    }
+
+    /// same as closest_points with configurable tolerances, see Tolerance
+    pub fn closest_points_with_tolerance(
+        &self,
+        other: Line2<F>,
+        tolerance: &Tolerance<F>,
+    ) -> (Vec2<F>, Vec2<F>) {
+        let closest_point = |line: &Line2<F>, pos| {
+            line.closest_point_with_parameter_and_tolerance(pos, tolerance)
+                .0
+        };
+        match self.intersection_with_overlap(other) {
+            LineIntersection::Point { pos, .. } => return (pos, pos),
+            LineIntersection::Overlap { overlap, .. } => return (overlap.start, overlap.start),
+            LineIntersection::None => {}
+        }
+
+        //without intersection the lines are either parallel or the distance is minimal at an end point
+        let end_points = |line: &Line2<F>| {
+            [line.start, line.end]
+                .into_iter()
+                .take(line.line_type.number_of_end_points())
+        };
+        let candidates = end_points(self)
+            .map(|pos| (pos, closest_point(&other, pos)))
+            .chain(end_points(&other).map(|pos| (closest_point(self, pos), pos)));
+
+        let mut closest = (self.start, closest_point(&other, self.start));
+        let mut min_distance = closest.0.euclidean_distance_squared(closest.1);
+        for (pos_self, pos_other) in candidates {
+            let distance = pos_self.euclidean_distance_squared(pos_other);
+            if distance < min_distance {
+                closest = (pos_self, pos_other);
+                min_distance = distance;
+            }
+        }
+        closest
+    }
+
+    /// the minimal distance between self and other, respecting their line types
+    pub fn distance_to_line(&self, other: Line2<F>) -> F {
+        self.distance_to_line_with_tolerance(other, &Tolerance::default())
+    }
+
+    /// same as distance_to_line with configurable tolerances, see Tolerance
+    pub fn distance_to_line_with_tolerance(&self, other: Line2<F>, tolerance: &Tolerance<F>) -> F {
+        let (pos_self, pos_other) = self.closest_points_with_tolerance(other, tolerance);
+        pos_self.euclidean_distance(pos_other)
+    }
+}
+
+impl<F: Float> Line2<F> {
+    /// the part of self inside the rectangle as a line segment, see clip_to_rectangle_with_parameters
+    pub fn clip_to_rectangle(&self, rectangle: &Rectangle<F>) -> Option<Line2<F>> {
+        self.clip_to_rectangle_with_parameters(rectangle)
+            .map(|(line_segment, _, _)| line_segment)
+    }
+
+    /// the part of self inside the rectangle (including its border) as a line segment
+    /// returns (line segment, entry parameter, exit parameter) or None if self misses the rectangle
+    /// the line segment is from start + (end - start) * entry to start + (end - start) * exit
+    /// lines without direction are clipped to (start, 0.0, 1.0) if start is inside the rectangle
+    pub fn clip_to_rectangle_with_parameters(
+        &self,
+        rectangle: &Rectangle<F>,
+    ) -> Option<(Line2<F>, F, F)> {
+        //the following algorithm is from Liang-Barsky
+        let direction = self.end - self.start;
+        if direction == Vec2::new(F::ZERO, F::ZERO) {
+            return rectangle.contains(self.start).then_some((
+                Line2::from_start_and_end(self.start, self.start, LineType::LineSegment),
+                F::ZERO,
+                F::ONE,
+            ));
+        }
+
+        let (mut entry, mut exit) = match self.line_type {
+            LineType::Line => (F::NEG_INFINITY, F::INFINITY),
+            LineType::Ray => (F::ZERO, F::INFINITY),
+            LineType::LineSegment => (F::ZERO, F::ONE),
+        };
+
+        //for every border: start + direction * t is inside if p * t <= q
+        let borders = [
+            (-direction.x(), self.start.x() - rectangle.left()),
+            (direction.x(), rectangle.right() - self.start.x()),
+            (-direction.y(), self.start.y() - rectangle.bottom()),
+            (direction.y(), rectangle.top() - self.start.y()),
+        ];
+        for (p, q) in borders {
+            if p == F::ZERO {
+                //parallel to the border
+                if q < F::ZERO {
+                    return None;
+                }
+            } else if p < F::ZERO {
+                entry = entry.max(q / p);
+            } else {
+                exit = exit.min(q / p);
+            }
+        }
+
+        (entry <= exit).then(|| {
+            (
+                Line2::from_start_and_end(
+                    self.start + direction * entry,
+                    self.start + direction * exit,
+                    LineType::LineSegment,
+                ),
+                entry,
+                exit,
+            )
+        })
+    }
+}
+
+impl From<Line2<f32>> for Line2<f64> {
+    fn from(line: Line2<f32>) -> Line2<f64> {
+        line.cast()
+    }
 }
@@ -0,0 +1,123 @@
+use core::ops::{Add, Mul, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{float::Float, Vec2};
+
+/// a 2x2 matrix stored as its two column vectors
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Mat2<F: Float = f32> {
+    x_axis: Vec2<F>,
+    y_axis: Vec2<F>,
+}
+
+impl<F: Float> Default for Mat2<F> {
+    fn default() -> Mat2<F> {
+        Mat2::identity()
+    }
+}
+
+impl<F: Float> Mat2<F> {
+    pub fn from_cols(x_axis: Vec2<F>, y_axis: Vec2<F>) -> Mat2<F> {
+        Mat2 { x_axis, y_axis }
+    }
+
+    pub fn from_rows(first_row: Vec2<F>, second_row: Vec2<F>) -> Mat2<F> {
+        Mat2::from_cols(
+            Vec2::new(first_row.x(), second_row.x()),
+            Vec2::new(first_row.y(), second_row.y()),
+        )
+    }
+
+    pub fn identity() -> Mat2<F> {
+        Mat2::from_scale(Vec2::new(F::ONE, F::ONE))
+    }
+
+    /// counter clockwise rotation, angle is in radians
+    pub fn from_angle(angle: F) -> Mat2<F> {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Mat2::from_cols(Vec2::new(cos, sin), Vec2::new(-sin, cos))
+    }
+
+    pub fn from_scale(scale: Vec2<F>) -> Mat2<F> {
+        Mat2::from_cols(Vec2::new(scale.x(), F::ZERO), Vec2::new(F::ZERO, scale.y()))
+    }
+
+    /// x' = x + shear_x * y and y' = y + shear_y * x
+    pub fn from_shear(shear_x: F, shear_y: F) -> Mat2<F> {
+        Mat2::from_cols(Vec2::new(F::ONE, shear_y), Vec2::new(shear_x, F::ONE))
+    }
+
+    /// converts the matrix into another precision
+    /// note: converting from f64 to f32 may lose precision
+    pub fn cast<G: Float>(self) -> Mat2<G> {
+        Mat2::from_cols(self.x_axis.cast(), self.y_axis.cast())
+    }
+
+    pub fn x_axis(&self) -> Vec2<F> {
+        self.x_axis
+    }
+
+    pub fn y_axis(&self) -> Vec2<F> {
+        self.y_axis
+    }
+
+    pub fn determinant(&self) -> F {
+        self.x_axis.x() * self.y_axis.y() - self.y_axis.x() * self.x_axis.y()
+    }
+
+    pub fn transpose(&self) -> Mat2<F> {
+        Mat2::from_rows(self.x_axis, self.y_axis)
+    }
+
+    /// returns None if the matrix is not invertible
+    pub fn inverse(&self) -> Option<Mat2<F>> {
+        let determinant = self.determinant();
+        if determinant == F::ZERO || !determinant.is_finite() {
+            return None;
+        }
+
+        let inverse = Mat2::from_cols(
+            Vec2::new(self.y_axis.y(), -self.x_axis.y()),
+            Vec2::new(-self.y_axis.x(), self.x_axis.x()),
+        ) * (F::ONE / determinant);
+        Some(inverse)
+    }
+}
+
+impl<F: Float> Mul<Vec2<F>> for Mat2<F> {
+    type Output = Vec2<F>;
+    fn mul(self, rhs: Vec2<F>) -> Vec2<F> {
+        self.x_axis * rhs.x() + self.y_axis * rhs.y()
+    }
+}
+
+impl<F: Float> Mul for Mat2<F> {
+    type Output = Mat2<F>;
+    fn mul(self, rhs: Mat2<F>) -> Mat2<F> {
+        Mat2::from_cols(self * rhs.x_axis, self * rhs.y_axis)
+    }
+}
+
+impl<F: Float> Mul<F> for Mat2<F> {
+    type Output = Mat2<F>;
+    fn mul(self, rhs: F) -> Mat2<F> {
+        Mat2::from_cols(self.x_axis * rhs, self.y_axis * rhs)
+    }
+}
+
+impl<F: Float> Add for Mat2<F> {
+    type Output = Mat2<F>;
+    fn add(self, rhs: Mat2<F>) -> Mat2<F> {
+        Mat2::from_cols(self.x_axis + rhs.x_axis, self.y_axis + rhs.y_axis)
+    }
+}
+
+impl<F: Float> Sub for Mat2<F> {
+    type Output = Mat2<F>;
+    fn sub(self, rhs: Mat2<F>) -> Mat2<F> {
+        Mat2::from_cols(self.x_axis - rhs.x_axis, self.y_axis - rhs.y_axis)
+    }
+}
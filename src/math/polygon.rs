@@ -0,0 +1,190 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    float::Float,
+    line2::Line2,
+    predicates::Orientation,
+    ray_cast::{first_hit, hits_on_line_segments, sorted_hits, RayHit},
+    rectangle::Rectangle,
+    trajectory::{Node, Trajectory},
+    Vec2,
+};
+
+/// a closed polygon, the last vertex is connected to the first one
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Polygon<F: Float = f32> {
+    vertices: Vec<Vec2<F>>,
+}
+
+/// twice the signed area of the triangle a, b, c
+/// positive if c is left of the line from a to b
+fn is_left<F: Float>(a: Vec2<F>, b: Vec2<F>, c: Vec2<F>) -> F {
+    (b.x() - a.x()) * (c.y() - a.y()) - (c.x() - a.x()) * (b.y() - a.y())
+}
+
+impl<F: Float> Polygon<F> {
+    pub fn new(vertices: Vec<Vec2<F>>) -> Polygon<F> {
+        Polygon { vertices }
+    }
+
+    /// uses the node positions as vertices
+    /// if the trajectory is closed (the last node is at the first node) the last node is skipped
+    pub fn from_trajectory<T: Trajectory<F>>(trajectory: &T) -> Polygon<F> {
+        let mut vertices: Vec<Vec2<F>> = trajectory.nodes().iter().map(Node::pos).collect();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Vec2<F>] {
+        &self.vertices
+    }
+
+    pub fn number_of_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// iterates over all edges (start, end) including the closing edge from the last to the first vertex
+    pub fn iter_edges(&self) -> impl Iterator<Item = (Vec2<F>, Vec2<F>)> + '_ {
+        let next_vertices = self.vertices.iter().skip(1).chain(self.vertices.first());
+        self.vertices.iter().copied().zip(next_vertices.copied())
+    }
+
+    /// positive if the polygon is counter clockwise, negative if it is clockwise
+    pub fn signed_area(&self) -> F {
+        //shoelace formula
+        let twice_area: F = self
+            .iter_edges()
+            .map(|(start, end)| start.x() * end.y() - end.x() * start.y())
+            .sum();
+        twice_area / F::TWO
+    }
+
+    pub fn area(&self) -> F {
+        self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> F {
+        self.iter_edges()
+            .map(|(start, end)| start.euclidean_distance(end))
+            .sum()
+    }
+
+    /// the center of mass of the polygon area
+    /// returns None if the polygon has no area
+    pub fn centroid(&self) -> Option<Vec2<F>> {
+        let signed_area = self.signed_area();
+        if signed_area == F::ZERO {
+            return None;
+        }
+
+        let mut x = F::ZERO;
+        let mut y = F::ZERO;
+        for (start, end) in self.iter_edges() {
+            let cross = start.x() * end.y() - end.x() * start.y();
+            x += (start.x() + end.x()) * cross;
+            y += (start.y() + end.y()) * cross;
+        }
+
+        let factor = F::ONE / (F::from_f32(6.0) * signed_area);
+        Some(Vec2::new(x * factor, y * factor))
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        let signed_area = self.signed_area();
+        if signed_area > F::ZERO {
+            Orientation::CounterClockwise
+        } else if signed_area < F::ZERO {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    pub fn reverse(&mut self) {
+        self.vertices.reverse();
+    }
+
+    /// reverses the vertices if the polygon does not have the given orientation
+    /// polygons without area are not changed
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        let current = self.orientation();
+        if current != Orientation::Collinear && current != orientation {
+            self.reverse();
+        }
+    }
+
+    /// point in polygon test with the even-odd rule
+    /// points on the border may be inside or outside
+    pub fn contains_even_odd(&self, pos: Vec2<F>) -> bool {
+        let mut inside = false;
+        for (start, end) in self.iter_edges() {
+            if (start.y() > pos.y()) != (end.y() > pos.y()) {
+                let x_crossing = start.x()
+                    + (pos.y() - start.y()) / (end.y() - start.y()) * (end.x() - start.x());
+                if pos.x() < x_crossing {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// how often the polygon winds around pos, positive for counter clockwise windings
+    pub fn winding_number(&self, pos: Vec2<F>) -> i32 {
+        //the following algorithm is from https://web.archive.org/web/20130126163405/http://geomalgorithms.com/a03-_inclusion.html
+        let mut winding_number = 0;
+        for (start, end) in self.iter_edges() {
+            if start.y() <= pos.y() {
+                if end.y() > pos.y() && is_left(start, end, pos) > F::ZERO {
+                    winding_number += 1;
+                }
+            } else if end.y() <= pos.y() && is_left(start, end, pos) < F::ZERO {
+                winding_number -= 1;
+            }
+        }
+        winding_number
+    }
+
+    /// point in polygon test with the non-zero winding rule
+    /// points on the border may be inside or outside
+    pub fn contains_non_zero(&self, pos: Vec2<F>) -> bool {
+        self.winding_number(pos) != 0
+    }
+
+    /// the first hit of ray on the border, the segment index is the index of the edge in iter_edges
+    pub fn ray_cast(&self, ray: Line2<F>) -> Option<RayHit<F>> {
+        first_hit(hits_on_line_segments(&ray, self.iter_edges(), true))
+    }
+
+    /// all hits of ray on the border sorted by the ray parameter, see ray_cast
+    pub fn ray_cast_all(&self, ray: Line2<F>) -> Vec<RayHit<F>> {
+        sorted_hits(hits_on_line_segments(&ray, self.iter_edges(), true))
+    }
+
+    /// returns None if the polygon has no vertices
+    pub fn bounding_rectangle(&self) -> Option<Rectangle<F>> {
+        let (&first, rest) = self.vertices.split_first()?;
+        let mut rectangle = Rectangle::new(first, first);
+        for &vertex in rest {
+            rectangle.extend_with_vec2(vertex);
+        }
+        Some(rectangle)
+    }
+}
+
+impl<F: Float> From<Vec<Vec2<F>>> for Polygon<F> {
+    fn from(vertices: Vec<Vec2<F>>) -> Polygon<F> {
+        Polygon::new(vertices)
+    }
+}
+
+impl<F: Float> FromIterator<Vec2<F>> for Polygon<F> {
+    fn from_iter<I: IntoIterator<Item = Vec2<F>>>(iter: I) -> Polygon<F> {
+        Polygon::new(iter.into_iter().collect())
+    }
+}
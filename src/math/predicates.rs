@@ -0,0 +1,432 @@
+//robust geometric predicates
+//the algorithms are from Jonathan Richard Shewchuk, "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates"
+//a fast floating point calculation is used if its error bound proves the sign, otherwise the determinant is calculated exactly
+//all coordinates have to be finite and small enough that their products do not overflow
+
+use super::{float::Float, Vec2};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    ///the points are on a line or the polygon has no area
+    Collinear,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CirclePosition {
+    Inside,
+    OnCircle,
+    Outside,
+}
+
+/// the orientation of the triangle a, b, c
+/// exact as long as the products of coordinate differences do not overflow (about 1e154 for f64)
+/// on overflow the result is not reliable and usually Collinear
+pub fn orientation<F: Float>(a: Vec2<F>, b: Vec2<F>, c: Vec2<F>) -> Orientation {
+    let determinant = cross_of_differences(a.cast(), c.cast(), b.cast(), c.cast());
+    if determinant > 0.0 {
+        Orientation::CounterClockwise
+    } else if determinant < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/// the position of d relative to the circle through a, b and c
+/// exact as long as the products of four coordinate differences do not overflow (about 1e77 for f64)
+/// returns None if a, b and c are collinear
+pub fn in_circle<F: Float>(
+    a: Vec2<F>,
+    b: Vec2<F>,
+    c: Vec2<F>,
+    d: Vec2<F>,
+) -> Option<CirclePosition> {
+    let mut determinant = in_circle_determinant(a.cast(), b.cast(), c.cast(), d.cast());
+    match orientation(a, b, c) {
+        Orientation::CounterClockwise => {}
+        Orientation::Clockwise => determinant = -determinant,
+        Orientation::Collinear => return None,
+    }
+
+    if determinant > 0.0 {
+        Some(CirclePosition::Inside)
+    } else if determinant < 0.0 {
+        Some(CirclePosition::Outside)
+    } else {
+        Some(CirclePosition::OnCircle)
+    }
+}
+
+///half an ulp of 1.0
+const EPSILON: f64 = f64::EPSILON / 2.0;
+///2^ceil(53 / 2) + 1, used to split a f64 into two halves with 26 bits each
+const SPLITTER: f64 = 134_217_729.0;
+const CROSS_ERROR_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const IN_CIRCLE_ERROR_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// (a - b) x (c - d), the returned value is an approximation with the exact sign
+pub(crate) fn cross_of_differences(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>, d: Vec2<f64>) -> f64 {
+    let left = (a.x() - b.x()) * (c.y() - d.y());
+    let right = (a.y() - b.y()) * (c.x() - d.x());
+    let determinant = left - right;
+
+    //if both products have different signs no cancellation is possible
+    let sum = if left > 0.0 {
+        if right <= 0.0 {
+            return determinant;
+        }
+        left + right
+    } else if left < 0.0 {
+        if right >= 0.0 {
+            return determinant;
+        }
+        -left - right
+    } else {
+        return determinant;
+    };
+
+    let error_bound = CROSS_ERROR_BOUND * sum;
+    if determinant >= error_bound || -determinant >= error_bound {
+        return determinant;
+    }
+
+    let left = Expansion::<2>::difference(a.x(), b.x())
+        .product::<2, 8>(&Expansion::difference(c.y(), d.y()));
+    let right = Expansion::<2>::difference(a.y(), b.y())
+        .product::<2, 8>(&Expansion::difference(c.x(), d.x()));
+    left.sum::<8, 16>(&right.negate()).estimate()
+}
+
+/// positive if d is inside the circle through the counter clockwise points a, b and c
+/// the returned value is an approximation with the exact sign
+fn in_circle_determinant(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>, d: Vec2<f64>) -> f64 {
+    let adx = a.x() - d.x();
+    let bdx = b.x() - d.x();
+    let cdx = c.x() - d.x();
+    let ady = a.y() - d.y();
+    let bdy = b.y() - d.y();
+    let cdy = c.y() - d.y();
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let determinant =
+        alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+
+    let error_bound = IN_CIRCLE_ERROR_BOUND * permanent;
+    if determinant > error_bound || -determinant > error_bound {
+        return determinant;
+    }
+
+    let adx = Expansion::<2>::difference(a.x(), d.x());
+    let bdx = Expansion::<2>::difference(b.x(), d.x());
+    let cdx = Expansion::<2>::difference(c.x(), d.x());
+    let ady = Expansion::<2>::difference(a.y(), d.y());
+    let bdy = Expansion::<2>::difference(b.y(), d.y());
+    let cdy = Expansion::<2>::difference(c.y(), d.y());
+
+    let lift = |dx: &Expansion<2>, dy: &Expansion<2>| {
+        dx.product::<2, 8>(dx).sum::<8, 16>(&dy.product::<2, 8>(dy))
+    };
+    let minor =
+        |dx_0: &Expansion<2>, dy_0: &Expansion<2>, dx_1: &Expansion<2>, dy_1: &Expansion<2>| {
+            dx_0.product::<2, 8>(dy_1)
+                .sum::<8, 16>(&dx_1.product::<2, 8>(dy_0).negate())
+        };
+
+    let a_term = lift(&adx, &ady).product::<16, 512>(&minor(&bdx, &bdy, &cdx, &cdy));
+    let b_term = lift(&bdx, &bdy).product::<16, 512>(&minor(&cdx, &cdy, &adx, &ady));
+    let c_term = lift(&cdx, &cdy).product::<16, 512>(&minor(&adx, &ady, &bdx, &bdy));
+
+    a_term
+        .sum::<512, 1024>(&b_term)
+        .sum::<512, 1536>(&c_term)
+        .estimate()
+}
+
+/// the exact sum of its terms
+/// the terms are nonoverlapping, sorted by increasing magnitude and not zero
+#[derive(Clone, Copy)]
+struct Expansion<const N: usize> {
+    terms: [f64; N],
+    len: usize,
+}
+
+/// a + b = x + y exactly, x is a + b rounded
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    let b_roundoff = b - b_virtual;
+    let a_roundoff = a - a_virtual;
+    (x, a_roundoff + b_roundoff)
+}
+
+/// a - b = x + y exactly, x is a - b rounded
+fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    let x = a - b;
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    let b_roundoff = b_virtual - b;
+    let a_roundoff = a - a_virtual;
+    (x, a_roundoff + b_roundoff)
+}
+
+/// a = high + low, both halves have at most 26 significant bits
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let a_big = c - a;
+    let high = c - a_big;
+    (high, a - high)
+}
+
+/// a * b = x + y exactly, x is a * b rounded
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_high, a_low) = split(a);
+    let (b_high, b_low) = split(b);
+    let error_1 = x - a_high * b_high;
+    let error_2 = error_1 - a_low * b_high;
+    let error_3 = error_2 - a_high * b_low;
+    (x, a_low * b_low - error_3)
+}
+
+impl<const N: usize> Expansion<N> {
+    fn zero() -> Expansion<N> {
+        Expansion {
+            terms: [0.0; N],
+            len: 0,
+        }
+    }
+
+    fn terms(&self) -> &[f64] {
+        &self.terms[..self.len]
+    }
+
+    ///zero terms are dropped
+    fn push(&mut self, term: f64) {
+        if term != 0.0 {
+            self.terms[self.len] = term;
+            self.len += 1;
+        }
+    }
+
+    fn difference(a: f64, b: f64) -> Expansion<N> {
+        let (x, y) = two_diff(a, b);
+        let mut expansion = Expansion::zero();
+        expansion.push(y);
+        expansion.push(x);
+        expansion
+    }
+
+    fn negate(mut self) -> Expansion<N> {
+        for term in &mut self.terms[..self.len] {
+            *term = -*term;
+        }
+        self
+    }
+
+    /// the sum of all terms, has the same sign as the exact value
+    fn estimate(&self) -> f64 {
+        self.terms().iter().sum()
+    }
+
+    /// self + other, M has to be at least N + K
+    fn sum<const K: usize, const M: usize>(&self, other: &Expansion<K>) -> Expansion<M> {
+        let e = self.terms();
+        let f = other.terms();
+        let mut result = Expansion::zero();
+
+        //merges both expansions by magnitude and sums them up from the smallest term
+        let (mut e_index, mut f_index) = (0, 0);
+        let mut next = || {
+            let take_e = f_index >= f.len()
+                || (e_index < e.len() && (f[f_index] > e[e_index]) == (f[f_index] > -e[e_index]));
+            if take_e {
+                e_index += 1;
+                e.get(e_index - 1).copied()
+            } else {
+                f_index += 1;
+                f.get(f_index - 1).copied()
+            }
+        };
+
+        if let Some(mut q) = next() {
+            while let Some(term) = next() {
+                let (new_q, error) = two_sum(q, term);
+                result.push(error);
+                q = new_q;
+            }
+            result.push(q);
+        }
+        result
+    }
+
+    /// self * b, M has to be at least 2 * N
+    fn scale<const M: usize>(&self, b: f64) -> Expansion<M> {
+        let mut result = Expansion::zero();
+        let Some((&first, rest)) = self.terms().split_first() else {
+            return result;
+        };
+
+        let (mut q, error) = two_product(first, b);
+        result.push(error);
+        for &term in rest {
+            let (product, product_error) = two_product(term, b);
+            let (sum, error) = two_sum(q, product_error);
+            result.push(error);
+            //product is bigger than sum, so a fast two sum would work too
+            let (new_q, error) = two_sum(product, sum);
+            result.push(error);
+            q = new_q;
+        }
+        result.push(q);
+        result
+    }
+
+    /// self * other, M has to be at least 2 * N * K
+    fn product<const K: usize, const M: usize>(&self, other: &Expansion<K>) -> Expansion<M> {
+        let mut result = Expansion::<M>::zero();
+        for &term in other.terms() {
+            result = result.sum::<M, M>(&self.scale::<M>(term));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///all coordinates are multiples of 2^-20 below 2^8, so they are exact integers after scaling
+    const SCALE: f64 = 1_048_576.0;
+
+    fn scaled(value: f64) -> i128 {
+        let scaled = value * SCALE;
+        assert_eq!(scaled.fract(), 0.0);
+        scaled as i128
+    }
+
+    fn exact_orientation(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>) -> Orientation {
+        let (acx, acy) = (scaled(a.x()) - scaled(c.x()), scaled(a.y()) - scaled(c.y()));
+        let (bcx, bcy) = (scaled(b.x()) - scaled(c.x()), scaled(b.y()) - scaled(c.y()));
+        match (acx * bcy - acy * bcx).signum() {
+            1 => Orientation::CounterClockwise,
+            -1 => Orientation::Clockwise,
+            _ => Orientation::Collinear,
+        }
+    }
+
+    fn exact_in_circle(
+        a: Vec2<f64>,
+        b: Vec2<f64>,
+        c: Vec2<f64>,
+        d: Vec2<f64>,
+    ) -> Option<CirclePosition> {
+        let row = |p: Vec2<f64>| {
+            let dx = scaled(p.x()) - scaled(d.x());
+            let dy = scaled(p.y()) - scaled(d.y());
+            (dx, dy, dx * dx + dy * dy)
+        };
+        let (adx, ady, alift) = row(a);
+        let (bdx, bdy, blift) = row(b);
+        let (cdx, cdy, clift) = row(c);
+        let determinant = alift * (bdx * cdy - cdx * bdy)
+            + blift * (cdx * ady - adx * cdy)
+            + clift * (adx * bdy - bdx * ady);
+        let sign = match exact_orientation(a, b, c) {
+            Orientation::CounterClockwise => determinant.signum(),
+            Orientation::Clockwise => -determinant.signum(),
+            Orientation::Collinear => return None,
+        };
+        match sign {
+            1 => Some(CirclePosition::Inside),
+            -1 => Some(CirclePosition::Outside),
+            _ => Some(CirclePosition::OnCircle),
+        }
+    }
+
+    #[test]
+    fn orientation_near_collinear() {
+        //points next to the line through b and c, the naive determinant gets many of these wrong
+        let unit = 1.0 / SCALE;
+        let b = Vec2::new(12.0, 12.0);
+        let c = Vec2::new(24.0, 24.0);
+        for i in 0..64 {
+            for j in 0..64 {
+                let a = Vec2::new(0.5 + i as f64 * unit, 0.5 + j as f64 * unit);
+                assert_eq!(orientation(a, b, c), exact_orientation(a, b, c), "{a:?}");
+                assert_eq!(orientation(b, a, c), exact_orientation(b, a, c), "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn orientation_collinear() {
+        let a = Vec2::new(0.125, 100.0);
+        let direction = Vec2::new(3.0 / SCALE, -7.0 / SCALE);
+        for i in 1..100 {
+            let b = a + direction * (i as f64);
+            let c = a + direction * (1000.0 * i as f64);
+            assert_eq!(orientation(a, b, c), Orientation::Collinear);
+            assert_eq!(orientation(c, a, b), Orientation::Collinear);
+        }
+    }
+
+    #[test]
+    fn in_circle_near_cocircular() {
+        let unit = 1.0 / SCALE;
+        //pseudo random centers and radii, the points are exactly on the circle
+        let mut seed = 12345_u64;
+        let mut next = |modulo: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % modulo
+        };
+        for _ in 0..200 {
+            let center = Vec2::new(
+                next(200) as f64 + next(1 << 20) as f64 * unit,
+                next(200) as f64 + next(1 << 20) as f64 * unit,
+            );
+            let radius = (1 + next(8)) as f64 + next(1 << 20) as f64 * unit;
+            let on_circle = |x: f64, y: f64| center + Vec2::new(x * radius, y * radius);
+            let a = on_circle(3.0, 4.0);
+            let b = on_circle(-5.0, 0.0);
+            let c = on_circle(0.0, -5.0);
+            let d = on_circle(4.0, -3.0);
+
+            assert_eq!(in_circle(a, b, c, d), Some(CirclePosition::OnCircle));
+            assert_eq!(in_circle(b, a, c, d), Some(CirclePosition::OnCircle));
+            for i in -2..=2 {
+                for j in -2..=2 {
+                    let d = d + Vec2::new(i as f64 * unit, j as f64 * unit);
+                    assert_eq!(in_circle(a, b, c, d), exact_in_circle(a, b, c, d), "{d:?}");
+                    assert_eq!(in_circle(c, b, a, d), exact_in_circle(c, b, a, d), "{d:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn in_circle_collinear() {
+        let a = Vec2::new(1.0, 1.0);
+        let b = Vec2::new(2.0, 2.0);
+        let c = Vec2::new(3.0, 3.0);
+        assert_eq!(in_circle(a, b, c, Vec2::new(0.0, 1.0)), None);
+    }
+}
@@ -0,0 +1,144 @@
+use core::cmp::Ordering;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use super::{
+    float::Float,
+    line2::{Line2, LineIntersection, LineType},
+    Vec2,
+};
+
+/// a point where a ray hits the border of a trajectory or shape
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit<F: Float = f32> {
+    pos: Vec2<F>,
+    ray_parameter: F,
+    segment_index: usize,
+    portion: F,
+    normal: Vec2<F>,
+}
+
+impl<F: Float> RayHit<F> {
+    pub(crate) fn new(
+        pos: Vec2<F>,
+        ray_parameter: F,
+        segment_index: usize,
+        portion: F,
+        normal: Vec2<F>,
+    ) -> RayHit<F> {
+        RayHit {
+            pos,
+            ray_parameter,
+            segment_index,
+            portion,
+            normal,
+        }
+    }
+
+    pub fn pos(&self) -> Vec2<F> {
+        self.pos
+    }
+
+    /// pos is ray.start() + (ray.end() - ray.start()) * ray_parameter
+    pub fn ray_parameter(&self) -> F {
+        self.ray_parameter
+    }
+
+    /// the index of the hit line segment or edge, always 0 for circles
+    pub fn segment_index(&self) -> usize {
+        self.segment_index
+    }
+
+    /// the portion on the hit line segment or edge in [0.0, 1.0]
+    /// for circles the counter clockwise angle from the positive x axis divided by 2 PI
+    pub fn portion(&self) -> F {
+        self.portion
+    }
+
+    /// the normalised surface normal at pos, pointing against the direction of the ray
+    pub fn normal(&self) -> Vec2<F> {
+        self.normal
+    }
+}
+
+/// the normalised normal of a surface with the given direction, pointing against the ray direction
+/// surfaces without direction face the ray
+pub(crate) fn facing_normal<F: Float>(surface: Vec2<F>, ray_direction: Vec2<F>) -> Vec2<F> {
+    match surface.try_get_orthogonally_vec() {
+        Some(normal) if normal * ray_direction > F::ZERO => -normal,
+        Some(normal) => normal,
+        None => (-ray_direction).normalise(),
+    }
+}
+
+/// the first hit of ray on the line segment from start to end
+/// collinear overlapping line segments are hit where the overlap starts
+pub(crate) fn hit_line_segment<F: Float>(
+    ray: &Line2<F>,
+    start: Vec2<F>,
+    end: Vec2<F>,
+    segment_index: usize,
+) -> Option<RayHit<F>> {
+    let line_segment = Line2::from_start_and_end(start, end, LineType::LineSegment);
+    let (pos, ray_parameter, portion) = match ray.intersection_with_overlap(line_segment) {
+        LineIntersection::None => return None,
+        LineIntersection::Point {
+            pos,
+            self_parameter,
+            other_parameter,
+        } => (pos, self_parameter, other_parameter),
+        LineIntersection::Overlap {
+            overlap,
+            self_parameters,
+            other_parameters,
+        } => (overlap.start(), self_parameters.0, other_parameters.0),
+    };
+    let normal = facing_normal(end - start, ray.end() - ray.start());
+    Some(RayHit::new(
+        pos,
+        ray_parameter,
+        segment_index,
+        portion,
+        normal,
+    ))
+}
+
+/// the hits of ray on consecutive line segments
+/// hits at the end of a line segment are only reported at the start of the following one
+/// if closed, the last line segment is followed by the first one
+pub(crate) fn hits_on_line_segments<'a, F: Float>(
+    ray: &'a Line2<F>,
+    line_segments: impl Iterator<Item = (Vec2<F>, Vec2<F>)> + 'a,
+    closed: bool,
+) -> impl Iterator<Item = RayHit<F>> + 'a {
+    let mut line_segments = line_segments.enumerate().peekable();
+    core::iter::from_fn(move || loop {
+        let (index, (start, end)) = line_segments.next()?;
+        let is_last = line_segments.peek().is_none();
+        if let Some(hit) = hit_line_segment(ray, start, end, index) {
+            if hit.portion < F::ONE || (is_last && !closed) {
+                return Some(hit);
+            }
+        }
+    })
+}
+
+/// orders hits by their ray parameter and then by their segment index
+fn compare<F: Float>(a: &RayHit<F>, b: &RayHit<F>) -> Ordering {
+    a.ray_parameter
+        .partial_cmp(&b.ray_parameter)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.segment_index.cmp(&b.segment_index))
+}
+
+pub(crate) fn first_hit<F: Float>(hits: impl Iterator<Item = RayHit<F>>) -> Option<RayHit<F>> {
+    hits.min_by(compare)
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn sorted_hits<F: Float>(hits: impl Iterator<Item = RayHit<F>>) -> Vec<RayHit<F>> {
+    let mut hits: Vec<RayHit<F>> = hits.collect();
+    hits.sort_by(compare);
+    hits
+}
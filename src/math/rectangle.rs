@@ -1,18 +1,31 @@
+#[cfg(feature = "egui")]
 use eframe::egui::Rect;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::max;
 
-use super::Vec2;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-#[derive(Clone, Copy, Default, Debug, Deserialize, Serialize)]
-pub struct Rectangle {
-    min: Vec2,
-    max: Vec2,
+#[cfg(feature = "alloc")]
+use super::ray_cast::sorted_hits;
+use super::{
+    float::Float,
+    line2::Line2,
+    ray_cast::{first_hit, hits_on_line_segments, RayHit},
+    Vec2,
+};
+
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Rectangle<F: Float = f32> {
+    min: Vec2<F>,
+    max: Vec2<F>,
 }
 
-impl Rectangle {
-    pub fn new(corner_a: Vec2, corner_b: Vec2) -> Rectangle {
+impl<F: Float> Rectangle<F> {
+    pub fn new(corner_a: Vec2<F>, corner_b: Vec2<F>) -> Rectangle<F> {
         let (min_x, max_x) = if corner_a.x() < corner_b.x() {
             (corner_a.x(), corner_b.x())
         } else {
@@ -30,63 +43,72 @@ impl Rectangle {
         Rectangle { min, max }
     }
 
-    pub fn from_center_and_size(center: Vec2, size: Vec2) -> Rectangle {
-        let corner_a = center + size / 2.0;
-        let corner_b = center - size / 2.0;
+    pub fn from_center_and_size(center: Vec2<F>, size: Vec2<F>) -> Rectangle<F> {
+        let corner_a = center + size / F::TWO;
+        let corner_b = center - size / F::TWO;
 
         Rectangle::new(corner_a, corner_b)
     }
 
-    pub fn min(&self) -> Vec2 {
+    /// converts the rectangle into another precision
+    /// note: converting from f64 to f32 may lose precision
+    pub fn cast<G: Float>(self) -> Rectangle<G> {
+        Rectangle {
+            min: self.min.cast(),
+            max: self.max.cast(),
+        }
+    }
+
+    pub fn min(&self) -> Vec2<F> {
         self.min
     }
 
-    pub fn max(&self) -> Vec2 {
+    pub fn max(&self) -> Vec2<F> {
         self.max
     }
 
-    pub fn center(&self) -> Vec2 {
-        (self.min + self.max) / 2.0
+    pub fn center(&self) -> Vec2<F> {
+        (self.min + self.max) / F::TWO
     }
 
-    pub fn top(&self) -> f32 {
+    pub fn top(&self) -> F {
         self.max.y()
     }
 
-    pub fn bottom(&self) -> f32 {
+    pub fn bottom(&self) -> F {
         self.min.y()
     }
 
-    pub fn right(&self) -> f32 {
+    pub fn right(&self) -> F {
         self.max.x()
     }
 
-    pub fn left(&self) -> f32 {
+    pub fn left(&self) -> F {
         self.min.x()
     }
 
-    pub fn width(&self) -> f32 {
+    pub fn width(&self) -> F {
         self.max().x() - self.min().x()
     }
 
-    pub fn height(&self) -> f32 {
+    pub fn height(&self) -> F {
         self.max().y() - self.min().y()
     }
 
     ///shrinks the rectangle in each direction, keeping the center
-    pub fn shrink(self, amount: f32) -> Rectangle {
+    pub fn shrink(self, amount: F) -> Rectangle<F> {
         let min = self.min + Vec2::new(amount, amount);
         let max = self.max - Vec2::new(amount, amount);
         Rectangle { min, max }
     }
 
-    pub fn shrink2(self, x_amount: f32, y_amount: f32) -> Rectangle {
+    pub fn shrink2(self, x_amount: F, y_amount: F) -> Rectangle<F> {
         let min = self.min + Vec2::new(x_amount, y_amount);
         let max = self.max - Vec2::new(x_amount, y_amount);
         Rectangle { min, max }
     }
 
-    pub fn extend_with_vec2(&mut self, vec: Vec2) {
+    pub fn extend_with_vec2(&mut self, vec: Vec2<F>) {
         let min_x = self.min.x().min(vec.x());
         let min_y = self.min.y().min(vec.y());
 
@@ -97,38 +119,164 @@ impl Rectangle {
         self.max = Vec2::from((max_x, max_y));
     }
 
-    pub fn extend_with_rectangle(&mut self, other: Rectangle) {
+    pub fn extend_with_rectangle(&mut self, other: Rectangle<F>) {
         self.extend_with_vec2(other.min);
         self.extend_with_vec2(other.max);
     }
 
-    pub fn contains(&self, pos: Vec2) -> bool {
+    /// the edges as (start, end), counter clockwise from the bottom edge: bottom, right, top, left
+    pub fn edges(&self) -> [(Vec2<F>, Vec2<F>); 4] {
+        let bottom_right = Vec2::new(self.right(), self.bottom());
+        let top_left = Vec2::new(self.left(), self.top());
+        [
+            (self.min, bottom_right),
+            (bottom_right, self.max),
+            (self.max, top_left),
+            (top_left, self.min),
+        ]
+    }
+
+    /// the first hit of ray on the border, the segment index is the index of the edge in edges
+    pub fn ray_cast(&self, ray: Line2<F>) -> Option<RayHit<F>> {
+        first_hit(hits_on_line_segments(&ray, self.edges().into_iter(), true))
+    }
+
+    /// all hits of ray on the border sorted by the ray parameter, see ray_cast
+    #[cfg(feature = "alloc")]
+    pub fn ray_cast_all(&self, ray: Line2<F>) -> Vec<RayHit<F>> {
+        sorted_hits(hits_on_line_segments(&ray, self.edges().into_iter(), true))
+    }
+
+    pub fn contains(&self, pos: Vec2<F>) -> bool {
         self.min.x() <= pos.x()
             && self.max.x() >= pos.x()
             && self.min.y() <= pos.y()
             && self.max.y() >= pos.y()
     }
 
-    pub fn distance_vector(self, other: Rectangle) -> Vec2 {
+    /// true if other is completely inside self, borders included
+    pub fn contains_rectangle(&self, other: Rectangle<F>) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// true if the rectangles share at least one point, touching borders included
+    pub fn intersects(&self, other: Rectangle<F>) -> bool {
+        self.min.x() <= other.max.x()
+            && other.min.x() <= self.max.x()
+            && self.min.y() <= other.max.y()
+            && other.min.y() <= self.max.y()
+    }
+
+    /// the overlapping part of both rectangles
+    /// returns None if they do not intersect, rectangles only touching result in a rectangle without area
+    pub fn intersection(&self, other: Rectangle<F>) -> Option<Rectangle<F>> {
+        self.intersects(other).then(|| Rectangle {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        })
+    }
+
+    /// the smallest rectangle containing both rectangles
+    pub fn union(&self, other: Rectangle<F>) -> Rectangle<F> {
+        let mut union = *self;
+        union.extend_with_rectangle(other);
+        union
+    }
+
+    pub fn area(&self) -> F {
+        self.width() * self.height()
+    }
+
+    /// the area of the intersection divided by the area of the union of both rectangles
+    /// 1.0 for identical rectangles, 0.0 for rectangles without overlap
+    /// the union area is the area covered by at least one of the rectangles, not the area of union()
+    /// returns 0.0 if both rectangles have no area
+    pub fn intersection_over_union(&self, other: Rectangle<F>) -> F {
+        let intersection_area = self
+            .intersection(other)
+            .map_or(F::ZERO, |intersection| intersection.area());
+        let union_area = self.area() + other.area() - intersection_area;
+        if union_area > F::ZERO {
+            intersection_area / union_area
+        } else {
+            F::ZERO
+        }
+    }
+
+    /// the point inside the rectangle closest to pos, pos itself if it is inside
+    pub fn clamp(&self, pos: Vec2<F>) -> Vec2<F> {
+        pos.clamp(self.min, self.max)
+    }
+
+    /// the horizontal and vertical gap between the rectangles, 0.0 in directions they overlap
+    pub fn distance_vector(self, other: Rectangle<F>) -> Vec2<F> {
         let x = max!(
-            other.bottom() - self.top(),
-            self.bottom() - other.top(),
-            0.0
-        );
-        let y = max!(
             other.left() - self.right(),
             self.left() - other.right(),
-            0.0
+            F::ZERO
+        );
+        let y = max!(
+            other.bottom() - self.top(),
+            self.bottom() - other.top(),
+            F::ZERO
         );
 
         Vec2::new(x, y)
     }
 
-    pub fn distance(self, other: Rectangle) -> f32 {
+    pub fn distance(self, other: Rectangle<F>) -> F {
         self.distance_vector(other).euclidean_lenght()
     }
+
+    /// 0.0 if pos is inside the rectangle
+    pub fn distance_to_point(&self, pos: Vec2<F>) -> F {
+        pos.euclidean_distance(self.clamp(pos))
+    }
+
+    /// the distance from pos to the border, negative if pos is inside the rectangle
+    pub fn signed_distance_to_point(&self, pos: Vec2<F>) -> F {
+        if self.contains(pos) {
+            -self.distance_to_border_from_inside(pos)
+        } else {
+            self.distance_to_point(pos)
+        }
+    }
+
+    /// the point on the border closest to pos, also for pos inside the rectangle
+    pub fn closest_point_on_border(&self, pos: Vec2<F>) -> Vec2<F> {
+        if !self.contains(pos) {
+            return self.clamp(pos);
+        }
+
+        //move pos to the closest edge
+        let distance = self.distance_to_border_from_inside(pos);
+        if pos.x() - self.left() == distance {
+            Vec2::new(self.left(), pos.y())
+        } else if self.right() - pos.x() == distance {
+            Vec2::new(self.right(), pos.y())
+        } else if pos.y() - self.bottom() == distance {
+            Vec2::new(pos.x(), self.bottom())
+        } else {
+            Vec2::new(pos.x(), self.top())
+        }
+    }
+
+    /// pos has to be inside the rectangle
+    fn distance_to_border_from_inside(&self, pos: Vec2<F>) -> F {
+        (pos.x() - self.left())
+            .min(self.right() - pos.x())
+            .min(pos.y() - self.bottom())
+            .min(self.top() - pos.y())
+    }
+}
+
+impl From<Rectangle<f32>> for Rectangle<f64> {
+    fn from(rectangle: Rectangle<f32>) -> Rectangle<f64> {
+        rectangle.cast()
+    }
 }
 
+#[cfg(feature = "egui")]
 impl From<Rectangle> for Rect {
     fn from(rectangle: Rectangle) -> Self {
         Rect {
@@ -138,8 +286,34 @@ impl From<Rectangle> for Rect {
     }
 }
 
+#[cfg(feature = "egui")]
 impl From<&Rectangle> for Rect {
     fn from(elem: &Rectangle) -> Rect {
         Rect::from(*elem)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_vector_separated_on_one_axis() {
+        let a = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+        let b = Rectangle::new(Vec2::new(3.0, 0.5), Vec2::new(4.0, 2.0));
+        assert_eq!(a.distance_vector(b), Vec2::new(2.0, 0.0));
+        assert_eq!(b.distance_vector(a), Vec2::new(2.0, 0.0));
+
+        let c = Rectangle::new(Vec2::new(0.5, -4.0), Vec2::new(2.0, -3.0));
+        assert_eq!(a.distance_vector(c), Vec2::new(0.0, 3.0));
+        assert_eq!(c.distance_vector(a), Vec2::new(0.0, 3.0));
+    }
+
+    #[test]
+    fn distance_vector_separated_on_both_axes() {
+        let a = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+        let b = Rectangle::new(Vec2::new(4.0, 5.0), Vec2::new(6.0, 7.0));
+        assert_eq!(a.distance_vector(b), Vec2::new(3.0, 4.0));
+        assert_eq!(a.distance(b), 5.0);
+    }
+}
@@ -0,0 +1,208 @@
+use alloc::vec::Vec;
+
+use crate::{Error, Float, Vec2};
+
+use super::{node::Node, segment_index::SegmentIndex, step_runner::Step, Trajectory};
+
+/// precomputed cumulative lengths of a trajectory
+/// answers queries between arc length (the distance along the trajectory from the first node) and positions
+pub struct ArcLengthIndex<'d, T: Trajectory<F>, F: Float = f32> {
+    trajectory: &'d T,
+    ///cumulative_lengths[i] is the arc length of node i
+    cumulative_lengths: Vec<F>,
+    ///finds the closest point for length_at_pos
+    segment_index: SegmentIndex<'d, T, F>,
+}
+
+impl<'d, T: Trajectory<F>, F: Float> ArcLengthIndex<'d, T, F> {
+    /// also builds a SegmentIndex for the trajectory
+    pub fn new(trajectory: &'d T) -> ArcLengthIndex<'d, T, F> {
+        ArcLengthIndex::from_segment_index(SegmentIndex::new(trajectory))
+    }
+
+    /// reuses an already built SegmentIndex
+    pub fn from_segment_index(segment_index: SegmentIndex<'d, T, F>) -> ArcLengthIndex<'d, T, F> {
+        let trajectory = segment_index.trajectory();
+        let mut cumulative_lengths = Vec::with_capacity(trajectory.number_of_nodes());
+        if !trajectory.nodes().is_empty() {
+            cumulative_lengths.push(F::ZERO);
+        }
+        let mut length = F::ZERO;
+        for line_segment in trajectory.iter_line_segments() {
+            length += line_segment.lenght();
+            cumulative_lengths.push(length);
+        }
+
+        ArcLengthIndex {
+            trajectory,
+            cumulative_lengths,
+            segment_index,
+        }
+    }
+
+    pub fn trajectory(&self) -> &'d T {
+        self.trajectory
+    }
+
+    pub fn segment_index(&self) -> &SegmentIndex<'d, T, F> {
+        &self.segment_index
+    }
+
+    pub fn total_length(&self) -> F {
+        self.cumulative_lengths.last().copied().unwrap_or(F::ZERO)
+    }
+
+    /// the arc length of the node at index
+    /// panics if index is out of bounds
+    pub fn length_at_node(&self, index: usize) -> F {
+        self.cumulative_lengths[index]
+    }
+
+    /// the length of the partial trajectory form start to end in O(1)
+    /// returns 0.0 if start > end, see try_track_length for a checked version
+    /// panics if start or end is out of bounds
+    pub fn track_length(&self, start: usize, end: usize) -> F {
+        if start > end {
+            return F::ZERO;
+        }
+        self.cumulative_lengths[end] - self.cumulative_lengths[start]
+    }
+
+    /// the length of the partial trajectory form start to end in O(1)
+    /// returns Error::InvalidRange if start > end and Error::IndexOutOfBounds if end is not a node index
+    pub fn try_track_length(&self, start: usize, end: usize) -> Result<F, Error> {
+        if start > end {
+            Err(Error::InvalidRange { start, end })
+        } else if end >= self.cumulative_lengths.len() {
+            Err(Error::IndexOutOfBounds {
+                index: end,
+                len: self.cumulative_lengths.len(),
+            })
+        } else {
+            Ok(self.track_length(start, end))
+        }
+    }
+
+    /// the arc length of the point on line segment index with portion t
+    /// as returned by closest_point_to_pos_with_portion_and_index
+    /// returns None if index is not a node index
+    pub fn length_at_portion(&self, index: usize, t: F) -> Option<F> {
+        let start = *self.cumulative_lengths.get(index)?;
+        match self.cumulative_lengths.get(index + 1) {
+            Some(&end) => Some(start + (end - start) * t),
+            None => Some(start),
+        }
+    }
+
+    /// the arc length of the closest point on the trajectory to pos
+    /// the closest point is found with the SegmentIndex, so this is O(log n) for typical trajectories
+    /// returns None if the trajectory is empty
+    pub fn length_at_pos(&self, pos: Vec2<F>) -> Option<F> {
+        let (_, index, t) = self
+            .segment_index
+            .closest_point_to_pos_with_portion_and_index(pos)?;
+        self.length_at_portion(index, t)
+    }
+
+    /// the step at arc length in O(log n)
+    /// length is clamped to [0.0, total_length]
+    /// returns None if the trajectory has less than two nodes
+    pub fn step_at_length(&self, length: F) -> Option<Step<F>> {
+        let nodes = self.trajectory.nodes();
+        if nodes.len() < 2 {
+            return None;
+        }
+
+        let total_length = self.total_length();
+        let length = length.max(F::ZERO).min(total_length);
+        //the start of the last line segment with length, trailing duplicate nodes are skipped
+        let last_index = self
+            .cumulative_lengths
+            .partition_point(|&node_length| node_length < total_length)
+            .saturating_sub(1)
+            .min(nodes.len() - 2);
+        //the last node whose arc length is <= length, this skips line segments without length
+        let index_0 = self
+            .cumulative_lengths
+            .partition_point(|&node_length| node_length <= length)
+            .saturating_sub(1)
+            .min(last_index);
+
+        let segment_length = self.track_length(index_0, index_0 + 1);
+        let t = if segment_length > F::ZERO {
+            ((length - self.cumulative_lengths[index_0]) / segment_length).min(F::ONE)
+        } else {
+            F::ZERO
+        };
+
+        Some(Step::new(
+            nodes[index_0].pos(),
+            index_0,
+            nodes[index_0 + 1].pos(),
+            t,
+        ))
+    }
+
+    /// the position at arc length in O(log n)
+    /// see step_at_length
+    pub fn pos_at_length(&self, length: F) -> Option<Vec2<F>> {
+        self.step_at_length(length).map(|step| step.position())
+    }
+
+    /// the position and the normalised tangent at arc length in O(log n)
+    /// see step_at_length, also returns None if the trajectory has no length
+    pub fn pos_and_tangent_at_length(&self, length: F) -> Option<(Vec2<F>, Vec2<F>)> {
+        let step = self.step_at_length(length)?;
+        let tangent = (step.pos_1() - step.pos_0()).try_normalise()?;
+        Some((step.position(), tangent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VecTrajectory;
+
+    #[test]
+    fn pos_and_tangent_at_end_with_trailing_duplicate() {
+        let trajectory = VecTrajectory::from_nodes(
+            0,
+            alloc::vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(10.0, 0.0),
+            ],
+        );
+        let index = ArcLengthIndex::new(&trajectory);
+        assert_eq!(index.total_length(), 10.0);
+        assert_eq!(
+            index.pos_and_tangent_at_length(10.0),
+            Some((Vec2::new(10.0, 0.0), Vec2::new(1.0, 0.0)))
+        );
+        assert_eq!(
+            index.pos_and_tangent_at_length(20.0),
+            Some((Vec2::new(10.0, 0.0), Vec2::new(1.0, 0.0)))
+        );
+        assert_eq!(
+            index.step_at_length(10.0).map(|step| step.index_0()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn track_length_of_reversed_range() {
+        let trajectory =
+            VecTrajectory::from_nodes(0, alloc::vec![Vec2::new(0.0, 0.0), Vec2::new(3.0, 4.0)]);
+        let index = ArcLengthIndex::new(&trajectory);
+        assert_eq!(index.track_length(0, 1), 5.0);
+        assert_eq!(index.track_length(1, 0), 0.0);
+        assert_eq!(
+            index.try_track_length(1, 0),
+            Err(Error::InvalidRange { start: 1, end: 0 })
+        );
+        assert_eq!(
+            index.try_track_length(0, 2),
+            Err(Error::IndexOutOfBounds { index: 2, len: 2 })
+        );
+    }
+}
@@ -1,16 +1,23 @@
-use crate::{Line2, LineType};
+use core::marker::PhantomData;
+
+use crate::{Float, Line2, LineType};
 
 use super::{node::Node, Trajectory};
 
 #[derive(Debug, Clone)]
-pub struct LineSegment<'n, N: Node> {
+pub struct LineSegment<'n, N: Node<F>, F: Float = f32> {
     start: &'n N,
     end: &'n N,
+    scalar: PhantomData<F>,
 }
 
-impl<'n, N: Node> LineSegment<'n, N> {
-    fn new(start: &'n N, end: &'n N) -> LineSegment<'n, N> {
-        LineSegment { start, end }
+impl<'n, N: Node<F>, F: Float> LineSegment<'n, N, F> {
+    fn new(start: &'n N, end: &'n N) -> LineSegment<'n, N, F> {
+        LineSegment {
+            start,
+            end,
+            scalar: PhantomData,
+        }
     }
 
     pub fn start(&self) -> &N {
@@ -21,13 +28,13 @@ impl<'n, N: Node> LineSegment<'n, N> {
         self.end
     }
 
-    pub fn lenght(&self) -> f32 {
+    pub fn lenght(&self) -> F {
         self.start().euclidean_distance(self.end())
     }
 }
 
-impl<'n, N: Node> From<LineSegment<'n, N>> for Line2 {
-    fn from(line_segment: LineSegment<'n, N>) -> Self {
+impl<'n, N: Node<F>, F: Float> From<LineSegment<'n, N, F>> for Line2<F> {
+    fn from(line_segment: LineSegment<'n, N, F>) -> Self {
         use LineType::LineSegment as LineSegmentType;
         Line2::from_start_and_end(
             line_segment.start().pos(),
@@ -37,23 +44,27 @@ impl<'n, N: Node> From<LineSegment<'n, N>> for Line2 {
     }
 }
 
-pub struct LineSegmentIter<'d, T: Trajectory> {
+pub struct LineSegmentIter<'d, T: Trajectory<F>, F: Float = f32> {
     trajectory: &'d T,
     segment_id: usize,
+    scalar: PhantomData<F>,
 }
 
-impl<'d, T: Trajectory> LineSegmentIter<'d, T> {
-    pub fn new(trajectory: &'d T) -> LineSegmentIter<T> {
+impl<'d, T: Trajectory<F>, F: Float> LineSegmentIter<'d, T, F> {
+    pub fn new(trajectory: &'d T) -> LineSegmentIter<'d, T, F> {
         LineSegmentIter {
             trajectory,
             segment_id: 0,
+            scalar: PhantomData,
         }
     }
 }
 
-impl<'d, N: Node + 'd, T: Trajectory<Node = N>> Iterator for LineSegmentIter<'d, T> {
-    type Item = LineSegment<'d, N>;
-    fn next(&mut self) -> Option<LineSegment<'d, N>> {
+impl<'d, F: Float, N: Node<F> + 'd, T: Trajectory<F, Node = N>> Iterator
+    for LineSegmentIter<'d, T, F>
+{
+    type Item = LineSegment<'d, N, F>;
+    fn next(&mut self) -> Option<LineSegment<'d, N, F>> {
         let node_a = self.trajectory.nodes().get(self.segment_id);
         let node_b = self.trajectory.nodes().get(self.segment_id + 1);
         self.segment_id += 1;
@@ -1,19 +1,44 @@
-use std::f32::{INFINITY, NAN};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
 
-use crate::{get_closest_point_on_line_segment_with_portion, Line2, Vec2};
+#[cfg(feature = "alloc")]
+use crate::math::ray_cast::sorted_hits;
+use crate::math::ray_cast::{first_hit, hits_on_line_segments};
+#[cfg(feature = "alloc")]
+use crate::{convex_hull, LineIntersection, Polygon, Rectangle};
+use crate::{get_closest_point_on_line_segment_with_portion, Error, Float, Line2, RayHit, Vec2};
 
+#[cfg(feature = "alloc")]
 pub use self::{
-    line_segment_iter::LineSegmentIter, node::Node, step_runner::StepRunner,
-    tangent_iter::TangentIter,
+    arc_length_index::ArcLengthIndex, segment_index::SegmentIndex, tangent_iter::TangentIter,
+    trajectory_mut::TrajectoryMut, vec_trajectory::VecTrajectory,
+};
+pub use self::{
+    line_segment_iter::LineSegmentIter,
+    node::Node,
+    step_runner::{Step, StepRunner},
 };
 
+#[cfg(feature = "alloc")]
+mod arc_length_index;
 mod line_segment_iter;
 mod node;
+#[cfg(feature = "alloc")]
+mod resample;
+#[cfg(feature = "alloc")]
+mod segment_index;
+#[cfg(feature = "alloc")]
+mod simplify;
 mod step_runner;
+#[cfg(feature = "alloc")]
 mod tangent_iter;
+#[cfg(feature = "alloc")]
+mod trajectory_mut;
+#[cfg(feature = "alloc")]
+mod vec_trajectory;
 
-pub trait Trajectory: Sized {
-    type Node: Node;
+pub trait Trajectory<F: Float = f32>: Sized {
+    type Node: Node<F>;
 
     fn id(&self) -> u64;
 
@@ -25,23 +50,36 @@ pub trait Trajectory: Sized {
         self.nodes().len()
     }
 
-    fn iter_line_segments(&self) -> LineSegmentIter<Self> {
+    fn iter_line_segments(&self) -> LineSegmentIter<'_, Self, F> {
         LineSegmentIter::new(self)
     }
 
-    fn get_step_runner(&self, step_length: f32) -> StepRunner<Self> {
+    fn get_step_runner(&self, step_length: F) -> StepRunner<'_, Self, F> {
         StepRunner::new(self, step_length)
     }
 
-    fn iter_tangents(&self) -> TangentIter<Self::Node> {
+    /// precomputes the cumulative lengths for fast arc length queries
+    #[cfg(feature = "alloc")]
+    fn arc_length_index(&self) -> ArcLengthIndex<'_, Self, F> {
+        ArcLengthIndex::new(self)
+    }
+
+    /// builds a bounding volume hierarchy over the line segments for fast closest segment queries
+    #[cfg(feature = "alloc")]
+    fn segment_index(&self) -> SegmentIndex<'_, Self, F> {
+        SegmentIndex::new(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn iter_tangents(&self) -> TangentIter<'_, Self::Node, F> {
         TangentIter::new(self)
     }
 
-    fn euclidean_distance_to_node<N: Node>(&self, node: &N) -> f32 {
+    fn euclidean_distance_to_node<N: Node<F>>(&self, node: &N) -> F {
         self.euclidean_distance_to_pos(node.pos())
     }
 
-    fn euclidean_distance_to_pos(&self, pos: Vec2) -> f32 {
+    fn euclidean_distance_to_pos(&self, pos: Vec2<F>) -> F {
         let closest_point = self.closest_point_to_pos(pos, |x| x);
         closest_point.euclidean_distance(pos)
     }
@@ -50,10 +88,14 @@ pub trait Trajectory: Sized {
     /// calculates the clostet point on the trajectory to pos in the new coordinate system
     /// returns the location vector to this closet point
     /// note: the returnet location vector is still in the new coordinate system
-    fn closest_point_to_pos(&self, pos: Vec2, pos_converter: impl Fn(Vec2) -> Vec2) -> Vec2 {
+    fn closest_point_to_pos(
+        &self,
+        pos: Vec2<F>,
+        pos_converter: impl Fn(Vec2<F>) -> Vec2<F>,
+    ) -> Vec2<F> {
         self.closest_point_to_pos_with_portion_and_index(pos, pos_converter)
             .map(|x| x.0)
-            .unwrap_or(Vec2::new(NAN, NAN))
+            .unwrap_or(Vec2::new(F::NAN, F::NAN))
     }
 
     /// calculates the clostet point on the trajectory to pos in the new coordinate system
@@ -63,17 +105,17 @@ pub trait Trajectory: Sized {
     /// retruns None if the Trajectory is empty
     fn closest_point_to_pos_with_portion_and_index(
         &self,
-        pos: Vec2,
-        pos_converter: impl Fn(Vec2) -> Vec2,
-    ) -> Option<(Vec2, usize, f32)> {
+        pos: Vec2<F>,
+        pos_converter: impl Fn(Vec2<F>) -> Vec2<F>,
+    ) -> Option<(Vec2<F>, usize, F)> {
         let pos = pos_converter(pos);
         match self.number_of_nodes() {
             0 => None,
-            1 => Some((pos_converter(self.nodes()[0].pos()), 0, 0.0)),
+            1 => Some((pos_converter(self.nodes()[0].pos()), 0, F::ZERO)),
             _ => {
-                let mut min_distance = INFINITY;
+                let mut min_distance = F::INFINITY;
                 let mut closest_point = self.nodes()[0].pos(); //dummy value
-                let mut closest_point_t = 0.0; //dummy value
+                let mut closest_point_t = F::ZERO; //dummy value
                 let mut closest_point_i = 0; //dummy value
                 for (i, line_segment) in self.iter_line_segments().enumerate() {
                     let line_start = pos_converter(line_segment.start().pos());
@@ -92,20 +134,21 @@ pub trait Trajectory: Sized {
                     }
                 }
 
-                if closest_point_t < 1.0 {
+                if closest_point_t < F::ONE {
                     Some((closest_point, closest_point_i, closest_point_t))
                 } else {
-                    Some((closest_point, closest_point_i + 1, 0.0))
+                    Some((closest_point, closest_point_i + 1, F::ZERO))
                 }
             }
         }
     }
 
-    fn euclidean_lenght(&self) -> f32 {
-        self.track_length(0, self.number_of_nodes() - 1)
+    fn euclidean_lenght(&self) -> F {
+        self.track_length(0, self.number_of_nodes().saturating_sub(1))
     }
 
-    fn intersections(&self, line: Line2) -> Vec<Vec2> {
+    #[cfg(feature = "alloc")]
+    fn intersections(&self, line: Line2<F>) -> Vec<Vec2<F>> {
         let mut intersections = Vec::new();
         for line_segment in self.iter_line_segments().map(Line2::from) {
             if let Some(intersection) = line.intersection(line_segment) {
@@ -115,13 +158,203 @@ pub trait Trajectory: Sized {
         intersections
     }
 
+    /// the parts of the trajectory inside the rectangle (including its border)
+    /// node_builder gets the position, the line segment index and the portion on the line segment in [0.0, 1.0)
+    /// of every node of the pieces and has to create the node, original nodes have the portion 0.0
+    /// the pieces have the id of self
+    #[cfg(feature = "alloc")]
+    fn clip_to_rectangle<N: Node<F>>(
+        &self,
+        rectangle: &Rectangle<F>,
+        mut node_builder: impl FnMut(Vec2<F>, usize, F) -> N,
+    ) -> Vec<VecTrajectory<N>> {
+        let mut pieces = Vec::new();
+        if let [node] = self.nodes() {
+            if rectangle.contains(node.pos()) {
+                let node = node_builder(node.pos(), 0, F::ZERO);
+                pieces.push(VecTrajectory::from_nodes(self.id(), vec![node]));
+            }
+            return pieces;
+        }
+
+        let mut current_piece: Option<Vec<N>> = None;
+        for (index, line_segment) in self.iter_line_segments().map(Line2::from).enumerate() {
+            let Some((clipped, entry, exit)) =
+                line_segment.clip_to_rectangle_with_parameters(rectangle)
+            else {
+                if let Some(nodes) = current_piece.take() {
+                    pieces.push(VecTrajectory::from_nodes(self.id(), nodes));
+                }
+                continue;
+            };
+
+            let mut node_at = |pos, portion| {
+                if portion >= F::ONE {
+                    node_builder(pos, index + 1, F::ZERO)
+                } else {
+                    node_builder(pos, index, portion)
+                }
+            };
+            let nodes = current_piece.get_or_insert_with(Vec::new);
+            if nodes.is_empty() {
+                nodes.push(node_at(clipped.start(), entry));
+            }
+            if exit > entry {
+                nodes.push(node_at(clipped.end(), exit));
+            }
+            if exit < F::ONE {
+                //the trajectory leaves the rectangle
+                if let Some(nodes) = current_piece.take() {
+                    pieces.push(VecTrajectory::from_nodes(self.id(), nodes));
+                }
+            }
+        }
+        if let Some(nodes) = current_piece {
+            pieces.push(VecTrajectory::from_nodes(self.id(), nodes));
+        }
+        pieces
+    }
+
+    /// the first hit of ray on the line segments of the trajectory
+    /// hits on nodes are reported at the start of the following line segment
+    fn ray_cast(&self, ray: Line2<F>) -> Option<RayHit<F>> {
+        first_hit(hits_on_line_segments(
+            &ray,
+            self.iter_line_segments()
+                .map(|line_segment| (line_segment.start().pos(), line_segment.end().pos())),
+            false,
+        ))
+    }
+
+    /// all hits of ray on the line segments sorted by the ray parameter, see ray_cast
+    #[cfg(feature = "alloc")]
+    fn ray_cast_all(&self, ray: Line2<F>) -> Vec<RayHit<F>> {
+        sorted_hits(hits_on_line_segments(
+            &ray,
+            self.iter_line_segments()
+                .map(|line_segment| (line_segment.start().pos(), line_segment.end().pos())),
+            false,
+        ))
+    }
+
+    /// the intersections of line with all line segments, including collinear overlaps
+    /// returns (line segment index, intersection) for all line segments intersecting line
+    /// self_parameter(s) refer to line and other_parameter(s) to the line segment
+    #[cfg(feature = "alloc")]
+    fn intersections_with_overlap(&self, line: Line2<F>) -> Vec<(usize, LineIntersection<F>)> {
+        self.iter_line_segments()
+            .map(Line2::from)
+            .enumerate()
+            .map(|(index, line_segment)| (index, line.intersection_with_overlap(line_segment)))
+            .filter(|(_, intersection)| *intersection != LineIntersection::None)
+            .collect()
+    }
+
     ///the length of the partial trajectory form start to end
-    fn track_length(&self, start: usize, end: usize) -> f32 {
-        assert!(start <= end);
-        let mut lenght = 0.0;
+    ///returns 0.0 if start > end, see try_track_length for a checked version
+    fn track_length(&self, start: usize, end: usize) -> F {
+        let mut lenght = F::ZERO;
+        if start > end {
+            return lenght;
+        }
         for line_segment in self.iter_line_segments().skip(start).take(end - start) {
             lenght += line_segment.lenght();
         }
         lenght
     }
+
+    ///the length of the partial trajectory form start to end
+    ///returns Error::InvalidRange if start > end and Error::IndexOutOfBounds if end is not a node index
+    fn try_track_length(&self, start: usize, end: usize) -> Result<F, Error> {
+        if start > end {
+            Err(Error::InvalidRange { start, end })
+        } else if end >= self.number_of_nodes() {
+            Err(Error::IndexOutOfBounds {
+                index: end,
+                len: self.number_of_nodes(),
+            })
+        } else {
+            Ok(self.track_length(start, end))
+        }
+    }
+
+    /// simplifies the trajectory with the Ramer-Douglas-Peucker algorithm
+    /// every removed node is at most tolerance away from the simplified trajectory
+    /// returns the indices of the kept nodes in ascending order, the first and last node are always kept
+    #[cfg(feature = "alloc")]
+    fn simplify_douglas_peucker(&self, tolerance: F) -> Vec<usize> {
+        let positions: Vec<Vec2<F>> = self.nodes().iter().map(Node::pos).collect();
+        simplify::douglas_peucker(&positions, tolerance)
+    }
+
+    /// simplifies the trajectory with the Visvalingam-Whyatt algorithm
+    /// nodes are removed as long as the triangle they form with their neighbours is smaller than area_threshold
+    /// returns the indices of the kept nodes in ascending order, the first and last node are always kept
+    #[cfg(feature = "alloc")]
+    fn simplify_visvalingam_whyatt(&self, area_threshold: F) -> Vec<usize> {
+        let positions: Vec<Vec2<F>> = self.nodes().iter().map(Node::pos).collect();
+        simplify::visvalingam_whyatt(&positions, area_threshold)
+    }
+
+    /// resamples the trajectory with a node every step_length (measured along the trajectory)
+    /// the first and last node of the trajectory are always part of the result
+    /// the last step is on the last line segment with t == 1.0
+    /// node_builder creates the new nodes from the step so attributes of custom nodes can be interpolated
+    /// returns an empty trajectory if self has less than two nodes
+    /// returns only the first and last node if step_length is not positive and finite
+    #[cfg(feature = "alloc")]
+    fn resample_by_distance<N: Node<F>>(
+        &self,
+        step_length: F,
+        node_builder: impl FnMut(Step<F>) -> N,
+    ) -> VecTrajectory<N> {
+        if self.number_of_nodes() < 2 {
+            return VecTrajectory::new(self.id());
+        }
+
+        let total_length = self.euclidean_lenght();
+        let valid_step_length = step_length > F::ZERO && step_length.is_finite();
+        //stop a bit before the end so we do not get a node right next to the last node due to rounding errors
+        let end = total_length - step_length * F::from_f32(0.001);
+        let mut distance = step_length;
+        let interior_distances = core::iter::from_fn(|| {
+            let current = distance;
+            distance += step_length;
+            (valid_step_length && current < end).then_some(current)
+        });
+        let distances = core::iter::once(F::ZERO).chain(interior_distances);
+
+        let steps = resample::steps_at_distances(self, distances);
+        VecTrajectory::from_nodes(self.id(), steps.into_iter().map(node_builder).collect())
+    }
+
+    /// resamples the trajectory to exactly number_of_nodes evenly spaced nodes
+    /// the first and last node of the trajectory are always part of the result
+    /// see resample_by_distance for node_builder
+    /// returns an empty trajectory if self has less than two nodes or number_of_nodes < 2
+    #[cfg(feature = "alloc")]
+    fn resample_by_count<N: Node<F>>(
+        &self,
+        number_of_nodes: usize,
+        node_builder: impl FnMut(Step<F>) -> N,
+    ) -> VecTrajectory<N> {
+        if self.number_of_nodes() < 2 || number_of_nodes < 2 {
+            return VecTrajectory::new(self.id());
+        }
+
+        let total_length = self.euclidean_lenght();
+        let intervals = F::from_f64((number_of_nodes - 1) as f64);
+        let distances =
+            (0..number_of_nodes - 1).map(|i| total_length * F::from_f64(i as f64) / intervals);
+
+        let steps = resample::steps_at_distances(self, distances);
+        VecTrajectory::from_nodes(self.id(), steps.into_iter().map(node_builder).collect())
+    }
+
+    /// the convex hull of the node positions
+    /// see convex_hull
+    #[cfg(feature = "alloc")]
+    fn convex_hull(&self) -> Polygon<F> {
+        convex_hull(self.nodes().iter().map(Node::pos))
+    }
 }
@@ -1,15 +1,15 @@
-use std::fmt::Debug;
+use core::fmt::Debug;
 
-use crate::Vec2;
+use crate::{Float, Vec2};
 
-pub trait Node: Debug + Clone {
-    fn pos(&self) -> Vec2;
+pub trait Node<F: Float = f32>: Debug + Clone {
+    fn pos(&self) -> Vec2<F>;
 
-    fn euclidean_distance<N: Node>(&self, other: &N) -> f32 {
+    fn euclidean_distance<N: Node<F>>(&self, other: &N) -> F {
         self.pos().euclidean_distance(other.pos())
     }
 
-    fn euclidean_distance_to_pos(&self, other: Vec2) -> f32 {
+    fn euclidean_distance_to_pos(&self, other: Vec2<F>) -> F {
         self.pos().euclidean_distance(other)
     }
 }
@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+
+use crate::Float;
+
+use super::{node::Node, step_runner::Step, Trajectory};
+
+/// creates a step for every distance (measured along the trajectory) plus one step at the end of the trajectory
+/// distances have to be ascending
+pub(super) fn steps_at_distances<F: Float, T: Trajectory<F>>(
+    trajectory: &T,
+    distances: impl Iterator<Item = F>,
+) -> Vec<Step<F>> {
+    let nodes = trajectory.nodes();
+    if nodes.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut steps = Vec::new();
+    let mut index_0 = 0;
+    let mut segment_start_length = F::ZERO;
+    let mut segment_length = nodes[0].euclidean_distance(&nodes[1]);
+
+    for distance in distances {
+        while segment_start_length + segment_length <= distance && index_0 + 2 < nodes.len() {
+            segment_start_length += segment_length;
+            index_0 += 1;
+            segment_length = nodes[index_0].euclidean_distance(&nodes[index_0 + 1]);
+        }
+
+        let t = if segment_length > F::ZERO {
+            ((distance - segment_start_length) / segment_length)
+                .max(F::ZERO)
+                .min(F::ONE)
+        } else {
+            F::ZERO
+        };
+
+        steps.push(Step::new(
+            nodes[index_0].pos(),
+            index_0,
+            nodes[index_0 + 1].pos(),
+            t,
+        ));
+    }
+
+    let last = nodes.len() - 1;
+    steps.push(Step::new(
+        nodes[last - 1].pos(),
+        last - 1,
+        nodes[last].pos(),
+        F::ONE,
+    ));
+
+    steps
+}
@@ -0,0 +1,309 @@
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+};
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use crate::{get_closest_point_on_line_segment_with_portion, Float, Rectangle, Vec2};
+
+use super::{node::Node, Trajectory};
+
+const MAX_SEGMENTS_PER_LEAF: usize = 4;
+
+enum BvhContent {
+    ///range in SegmentIndex::segment_order
+    Leaf {
+        start: usize,
+        end: usize,
+    },
+    Inner {
+        left: usize,
+        right: usize,
+    },
+}
+
+struct BvhNode<F: Float> {
+    bounds: Rectangle<F>,
+    content: BvhContent,
+}
+
+/// a bounding volume hierarchy over the line segments of a trajectory
+/// accelerates closest segment queries on large trajectories
+pub struct SegmentIndex<'d, T: Trajectory<F>, F: Float = f32> {
+    trajectory: &'d T,
+    ///the root is the first node, empty if the trajectory has no line segments
+    bvh_nodes: Vec<BvhNode<F>>,
+    ///the line segment indices, ordered so each leaf covers a continuous range
+    segment_order: Vec<usize>,
+}
+
+fn distance_squared_to_rectangle<F: Float>(rectangle: &Rectangle<F>, pos: Vec2<F>) -> F {
+    let dx = (rectangle.left() - pos.x())
+        .max(pos.x() - rectangle.right())
+        .max(F::ZERO);
+    let dy = (rectangle.bottom() - pos.y())
+        .max(pos.y() - rectangle.top())
+        .max(F::ZERO);
+    dx * dx + dy * dy
+}
+
+/// a line segment found by a query
+/// ordered by distance and then by index
+struct Hit<F: Float> {
+    distance: F,
+    point: Vec2<F>,
+    index: usize,
+    t: F,
+}
+
+impl<F: Float> PartialEq for Hit<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: Float> Eq for Hit<F> {}
+
+impl<F: Float> PartialOrd for Hit<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for Hit<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .partial_cmp(&other.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+impl<'d, T: Trajectory<F>, F: Float> SegmentIndex<'d, T, F> {
+    pub fn new(trajectory: &'d T) -> SegmentIndex<'d, T, F> {
+        let number_of_segments = trajectory.number_of_nodes().saturating_sub(1);
+        let mut index = SegmentIndex {
+            trajectory,
+            bvh_nodes: Vec::new(),
+            segment_order: (0..number_of_segments).collect(),
+        };
+
+        if number_of_segments > 0 {
+            index.build(0, number_of_segments);
+        }
+
+        index
+    }
+
+    pub fn trajectory(&self) -> &'d T {
+        self.trajectory
+    }
+
+    fn segment(&self, index: usize) -> (Vec2<F>, Vec2<F>) {
+        let nodes = self.trajectory.nodes();
+        (nodes[index].pos(), nodes[index + 1].pos())
+    }
+
+    fn segment_bounds(&self, index: usize) -> Rectangle<F> {
+        let (start, end) = self.segment(index);
+        Rectangle::new(start, end)
+    }
+
+    /// builds the subtree for segment_order[start..end] and returns the index of its root
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let mut bounds = self.segment_bounds(self.segment_order[start]);
+        for &segment in &self.segment_order[start + 1..end] {
+            bounds.extend_with_rectangle(self.segment_bounds(segment));
+        }
+
+        let node_index = self.bvh_nodes.len();
+        self.bvh_nodes.push(BvhNode {
+            bounds,
+            content: BvhContent::Leaf { start, end },
+        });
+
+        if end - start > MAX_SEGMENTS_PER_LEAF {
+            //split at the median of the segment centers along the longer axis
+            let split_along_x = bounds.width() >= bounds.height();
+            let center = |segment: usize| {
+                let (start, end) = self.segment(segment);
+                let center = (start + end) / F::TWO;
+                if split_along_x {
+                    center.x()
+                } else {
+                    center.y()
+                }
+            };
+            let mut segments = self.segment_order[start..end].to_vec();
+            segments.sort_by(|&a, &b| {
+                center(a)
+                    .partial_cmp(&center(b))
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| a.cmp(&b))
+            });
+            self.segment_order[start..end].copy_from_slice(&segments);
+
+            let middle = start + (end - start) / 2;
+            let left = self.build(start, middle);
+            let right = self.build(middle, end);
+            self.bvh_nodes[node_index].content = BvhContent::Inner { left, right };
+        }
+
+        node_index
+    }
+
+    fn hit(&self, segment: usize, pos: Vec2<F>) -> Hit<F> {
+        let (start, end) = self.segment(segment);
+        let (point, t) = get_closest_point_on_line_segment_with_portion(start, end, pos);
+        Hit {
+            distance: pos.euclidean_distance(point),
+            point,
+            index: segment,
+            t,
+        }
+    }
+
+    /// visits all line segments in leaves whose bounds are not farther away from pos than max_distance() returns
+    fn visit(
+        &self,
+        pos: Vec2<F>,
+        mut max_distance: impl FnMut() -> F,
+        mut visitor: impl FnMut(Hit<F>),
+    ) {
+        if self.bvh_nodes.is_empty() {
+            return;
+        }
+
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.bvh_nodes[node_index];
+            //the slack keeps line segments with the same distance as the current candidates
+            //even if the distance to the bounds is rounded differently
+            let max_distance = max_distance() * (F::ONE + F::from_f32(16.0) * F::EPSILON);
+            if distance_squared_to_rectangle(&node.bounds, pos) > max_distance * max_distance {
+                continue;
+            }
+
+            match node.content {
+                BvhContent::Leaf { start, end } => {
+                    for &segment in &self.segment_order[start..end] {
+                        visitor(self.hit(segment, pos));
+                    }
+                }
+                BvhContent::Inner { left, right } => {
+                    //visit the closer child first so the other one can be pruned more often
+                    let left_distance =
+                        distance_squared_to_rectangle(&self.bvh_nodes[left].bounds, pos);
+                    let right_distance =
+                        distance_squared_to_rectangle(&self.bvh_nodes[right].bounds, pos);
+                    if left_distance <= right_distance {
+                        stack.push(right);
+                        stack.push(left);
+                    } else {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+    }
+
+    /// same as Trajectory::closest_point_to_pos_with_portion_and_index without a pos_converter
+    /// returns the (coordinate, the line segment index, the portion on the line segment in [0.0, 1.0)
+    /// returns None if the Trajectory is empty
+    pub fn closest_point_to_pos_with_portion_and_index(
+        &self,
+        pos: Vec2<F>,
+    ) -> Option<(Vec2<F>, usize, F)> {
+        match self.trajectory.number_of_nodes() {
+            0 => None,
+            1 => Some((self.trajectory.nodes()[0].pos(), 0, F::ZERO)),
+            _ => {
+                let mut closest: Option<Hit<F>> = None;
+                let best_distance = Cell::new(F::INFINITY);
+                self.visit(
+                    pos,
+                    || best_distance.get(),
+                    |hit| {
+                        if closest.as_ref().is_none_or(|closest| hit < *closest) {
+                            best_distance.set(hit.distance);
+                            closest = Some(hit);
+                        }
+                    },
+                );
+
+                closest.map(|closest| {
+                    if closest.t < F::ONE {
+                        (closest.point, closest.index, closest.t)
+                    } else {
+                        (closest.point, closest.index + 1, F::ZERO)
+                    }
+                })
+            }
+        }
+    }
+
+    pub fn closest_point_to_pos(&self, pos: Vec2<F>) -> Option<Vec2<F>> {
+        self.closest_point_to_pos_with_portion_and_index(pos)
+            .map(|x| x.0)
+    }
+
+    /// the k line segments closest to pos
+    /// returns (closest point on the line segment, line segment index, portion on the line segment in [0.0, 1.0])
+    /// sorted by the distance to pos
+    pub fn k_nearest_segments(&self, pos: Vec2<F>, k: usize) -> Vec<(Vec2<F>, usize, F)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        //max heap, the root is the farthest of the k closest hits found so far
+        let heap = RefCell::new(BinaryHeap::<Hit<F>>::with_capacity(k + 1));
+        self.visit(
+            pos,
+            || {
+                let heap = heap.borrow();
+                if heap.len() < k {
+                    F::INFINITY
+                } else {
+                    heap.peek()
+                        .map_or(F::INFINITY, |farthest| farthest.distance)
+                }
+            },
+            |hit| {
+                let mut heap = heap.borrow_mut();
+                heap.push(hit);
+                if heap.len() > k {
+                    heap.pop();
+                }
+            },
+        );
+
+        heap.into_inner()
+            .into_sorted_vec()
+            .into_iter()
+            .map(|hit| (hit.point, hit.index, hit.t))
+            .collect()
+    }
+
+    /// all line segments with a distance to pos <= radius
+    /// returns (closest point on the line segment, line segment index, portion on the line segment in [0.0, 1.0])
+    /// sorted by the line segment index
+    pub fn segments_within_radius(&self, pos: Vec2<F>, radius: F) -> Vec<(Vec2<F>, usize, F)> {
+        let mut hits = Vec::new();
+        self.visit(
+            pos,
+            || radius,
+            |hit| {
+                if hit.distance <= radius {
+                    hits.push(hit);
+                }
+            },
+        );
+
+        hits.sort_by_key(|hit| hit.index);
+        hits.into_iter()
+            .map(|hit| (hit.point, hit.index, hit.t))
+            .collect()
+    }
+}
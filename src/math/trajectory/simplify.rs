@@ -0,0 +1,146 @@
+use core::cmp::Ordering;
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use crate::{point_line_segment_distance, Float, Vec2};
+
+/// Ramer-Douglas-Peucker
+/// returns the indices of the kept positions in ascending order
+pub(super) fn douglas_peucker<F: Float>(positions: &[Vec2<F>], tolerance: F) -> Vec<usize> {
+    if positions.len() < 3 {
+        return (0..positions.len()).collect();
+    }
+
+    let mut keep = vec![false; positions.len()];
+    keep[0] = true;
+    keep[positions.len() - 1] = true;
+
+    //we use a stack instead of recursion so long trajectories can not overflow the call stack
+    let mut stack = vec![(0, positions.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_distance = F::ZERO;
+        let mut max_index = start;
+        for (i, &pos) in positions.iter().enumerate().take(end).skip(start + 1) {
+            let distance = point_line_segment_distance(positions[start], positions[end], pos);
+            if distance > max_distance {
+                max_distance = distance;
+                max_index = i;
+            }
+        }
+
+        if max_distance > tolerance {
+            keep[max_index] = true;
+            stack.push((start, max_index));
+            stack.push((max_index, end));
+        }
+    }
+
+    keep.iter()
+        .enumerate()
+        .filter(|(_, &keep)| keep)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn triangle_area<F: Float>(a: Vec2<F>, b: Vec2<F>, c: Vec2<F>) -> F {
+    let ab = b - a;
+    let ac = c - a;
+    (ab.x() * ac.y() - ab.y() * ac.x()).abs() / F::TWO
+}
+
+struct Candidate<F: Float> {
+    area: F,
+    index: usize,
+    version: usize,
+}
+
+impl<F: Float> PartialEq for Candidate<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: Float> Eq for Candidate<F> {}
+
+impl<F: Float> PartialOrd for Candidate<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for Candidate<F> {
+    //reversed so the BinaryHeap pops the smallest area first
+    //ties are broken by the index to get a deterministic result
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// Visvalingam-Whyatt
+/// removes positions whose effective triangle area is smaller than area_threshold
+/// returns the indices of the kept positions in ascending order
+pub(super) fn visvalingam_whyatt<F: Float>(positions: &[Vec2<F>], area_threshold: F) -> Vec<usize> {
+    let len = positions.len();
+    if len < 3 {
+        return (0..len).collect();
+    }
+
+    //doubly linked list over the remaining positions
+    let mut previous: Vec<usize> = (0..len).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (0..len).map(|i| i + 1).collect();
+    let mut removed = vec![false; len];
+    //a candidate in the heap is outdated if its version does not match
+    let mut versions = vec![0; len];
+
+    let mut heap: BinaryHeap<Candidate<F>> = (1..len - 1)
+        .map(|index| Candidate {
+            area: triangle_area(positions[index - 1], positions[index], positions[index + 1]),
+            index,
+            version: 0,
+        })
+        .collect();
+
+    let mut last_area = F::ZERO;
+    while let Some(candidate) = heap.pop() {
+        if candidate.version != versions[candidate.index] {
+            continue;
+        }
+        if candidate.area >= area_threshold {
+            break;
+        }
+
+        let index = candidate.index;
+        removed[index] = true;
+        //the effective area never decreases, so removing a point can not
+        //make a neighbour cheaper to remove than the point itself
+        last_area = last_area.max(candidate.area);
+
+        let prev = previous[index];
+        let nxt = next[index];
+        next[prev] = nxt;
+        previous[nxt] = prev;
+
+        for neighbour in [prev, nxt] {
+            if neighbour == 0 || neighbour == len - 1 {
+                continue;
+            }
+            versions[neighbour] += 1;
+            let area = triangle_area(
+                positions[previous[neighbour]],
+                positions[neighbour],
+                positions[next[neighbour]],
+            );
+            heap.push(Candidate {
+                area: area.max(last_area),
+                index: neighbour,
+                version: versions[neighbour],
+            });
+        }
+    }
+
+    (0..len).filter(|&i| !removed[i]).collect()
+}
@@ -1,22 +1,29 @@
-use crate::{math::trajectory::node::Node, Vec2};
+use crate::{math::trajectory::node::Node, Error, Float, Tolerance, Vec2};
 
 use super::Trajectory;
 
-const EPSILON: f32 = 0.001;
-
 #[derive(Clone, Copy, Debug)]
-pub struct Step {
-    pos_0: Vec2,
+pub struct Step<F: Float = f32> {
+    pos_0: Vec2<F>,
     index_0: usize,
-    pos_1: Vec2,
-    t: f32,
+    pos_1: Vec2<F>,
+    t: F,
 }
 
-impl Step {
+impl<F: Float> Step<F> {
+    pub(super) fn new(pos_0: Vec2<F>, index_0: usize, pos_1: Vec2<F>, t: F) -> Step<F> {
+        Step {
+            pos_0,
+            index_0,
+            pos_1,
+            t,
+        }
+    }
+
     /// Get the steps actual position
     /// step.position() == step.pos_0() * (1 - step.t()) + step.pos_1() * step.t()
-    pub fn position(&self) -> Vec2 {
-        self.pos_0 * (1.0 - self.t) + self.pos_1 * self.t
+    pub fn position(&self) -> Vec2<F> {
+        self.pos_0 * (F::ONE - self.t) + self.pos_1 * self.t
     }
 
     /// Get the step's index 0.
@@ -30,45 +37,66 @@ impl Step {
     }
 
     /// Get the step's pos 0.
-    pub fn pos_0(&self) -> Vec2 {
+    pub fn pos_0(&self) -> Vec2<F> {
         self.pos_0
     }
 
     /// Get the step's pos 1.
-    pub fn pos_1(&self) -> Vec2 {
+    pub fn pos_1(&self) -> Vec2<F> {
         self.pos_1
     }
 
     /// Get the step's t.
     /// t is in [0.0, 1.0) and indicates how far we moved from pos_0 to pos_1 in this step
-    pub fn t(&self) -> f32 {
+    /// note: steps at the very end of a trajectory (e.g. the last step of a resampled trajectory) have t == 1.0
+    pub fn t(&self) -> F {
         self.t
     }
 
-    fn remaining_lenght(&self) -> f32 {
+    fn remaining_lenght(&self) -> F {
         self.position().euclidean_distance(self.pos_1)
     }
 }
 
-pub struct StepRunner<'d, T: Trajectory> {
+pub struct StepRunner<'d, T: Trajectory<F>, F: Float = f32> {
     trajectory: &'d T,
-    step_length: f32,
-    last_step: Option<Step>,
+    step_length: F,
+    tolerance: Tolerance<F>,
+    last_step: Option<Step<F>>,
 }
 
-impl<'d, T: Trajectory> StepRunner<'d, T> {
-    pub fn new(trajectory: &'d T, step_length: f32) -> StepRunner<'d, T> {
+impl<'d, T: Trajectory<F>, F: Float> StepRunner<'d, T, F> {
+    pub fn new(trajectory: &'d T, step_length: F) -> StepRunner<'d, T, F> {
+        StepRunner::with_tolerance(trajectory, step_length, Tolerance::default())
+    }
+
+    /// uses tolerance.step_overshoot for the numerical self check of try_next
+    pub fn with_tolerance(
+        trajectory: &'d T,
+        step_length: F,
+        tolerance: Tolerance<F>,
+    ) -> StepRunner<'d, T, F> {
         StepRunner {
             trajectory,
             step_length,
+            tolerance,
             last_step: None,
         }
     }
-}
 
-impl<'d, T: Trajectory> Iterator for StepRunner<'d, T> {
-    type Item = Step;
-    fn next(&mut self) -> Option<Step> {
+    /// the fallible version of next
+    /// returns Error::InvalidStepLength if the step length is not positive and finite
+    /// returns Error::NumericalInstability if the step could not be calculated reliably
+    /// returns Ok(None) if the end of the trajectory is reached
+    pub fn try_next(&mut self) -> Result<Option<Step<F>>, Error> {
+        self.next_step().transpose()
+    }
+
+    fn next_step(&mut self) -> Option<Result<Step<F>, Error>> {
+        if self.step_length <= F::ZERO || !self.step_length.is_finite() {
+            return Some(Err(Error::InvalidStepLength));
+        }
+
         if let Some(mut current_step) = self.last_step {
             let mut remaining_step_length = self.step_length;
 
@@ -81,7 +109,7 @@ impl<'d, T: Trajectory> Iterator for StepRunner<'d, T> {
                     pos_0: self.trajectory.nodes().get(new_index_0)?.pos(),
                     index_0: new_index_0,
                     pos_1: self.trajectory.nodes().get(new_index_0 + 1)?.pos(),
-                    t: 0.0,
+                    t: F::ZERO,
                 };
 
                 current_step = new_step;
@@ -89,15 +117,12 @@ impl<'d, T: Trajectory> Iterator for StepRunner<'d, T> {
             let line_segment_lenght = current_step.pos_0.euclidean_distance(current_step.pos_1);
             let new_t = remaining_step_length / line_segment_lenght + current_step.t;
 
-            assert!(new_t >= 0.0);
-
-            if new_t > 1.0 + EPSILON {
-                dbg!(new_t);
-                dbg!(current_step);
+            //this also catches NaN
+            if !(F::ZERO..=F::ONE + self.tolerance.step_overshoot).contains(&new_t) {
+                return Some(Err(Error::NumericalInstability));
             }
-            assert!(new_t <= 1.0 + EPSILON);
 
-            let new_step = if new_t >= 1.0 {
+            let new_step = if new_t >= F::ONE {
                 Step {
                     pos_0: self
                         .trajectory
@@ -110,7 +135,7 @@ impl<'d, T: Trajectory> Iterator for StepRunner<'d, T> {
                         .nodes()
                         .get(current_step.index_1() + 1)?
                         .pos(),
-                    t: 0.0,
+                    t: F::ZERO,
                 }
             } else {
                 Step {
@@ -120,18 +145,26 @@ impl<'d, T: Trajectory> Iterator for StepRunner<'d, T> {
             };
             self.last_step = Some(new_step);
 
-            Some(new_step)
+            Some(Ok(new_step))
         } else {
-            let step = Step {
-                pos_0: self.trajectory.nodes().get(0)?.pos(),
-                pos_1: self.trajectory.nodes().get(1)?.pos(),
-                index_0: 0,
-                t: 0.0,
-            };
+            let step = Step::new(
+                self.trajectory.nodes().first()?.pos(),
+                0,
+                self.trajectory.nodes().get(1)?.pos(),
+                F::ZERO,
+            );
 
             self.last_step = Some(step);
 
-            Some(step)
+            Some(Ok(step))
         }
     }
 }
+
+/// stops at the end of the trajectory or at the first error, see try_next
+impl<'d, T: Trajectory<F>, F: Float> Iterator for StepRunner<'d, T, F> {
+    type Item = Step<F>;
+    fn next(&mut self) -> Option<Step<F>> {
+        self.next_step().and_then(Result::ok)
+    }
+}
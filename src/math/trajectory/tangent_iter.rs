@@ -1,51 +1,67 @@
-use crate::{Line2, LineType, Vec2};
+use alloc::boxed::Box;
+
+use crate::{Float, Line2, LineType, Vec2};
 
 use super::{node::Node, Trajectory};
 
-pub struct TangentIter<'d, N: Node> {
-    line_segment_buffer: Option<Vec2>,
-    line_segment_iter: Box<(dyn Iterator<Item = Vec2> + 'd)>,
-    node_iter: Box<(dyn Iterator<Item = &'d N> + 'd)>,
+/// iterates over the tangents at the nodes of a trajectory
+/// a node at the same position as its predecessor gets the direction of the last line segment with a length
+/// nodes before the first line segment with a length get the direction of that line segment
+/// so there is one tangent per node, except for trajectories without any line segment with a length which have none
+pub struct TangentIter<'d, N: Node<F>, F: Float = f32> {
+    ///the normalised direction of the last line segment with a length
+    line_segment_buffer: Option<Vec2<F>>,
+    ///the normalised direction of the first line segment with a length
+    first_direction: Option<Vec2<F>>,
+    line_segment_iter: Box<dyn Iterator<Item = Vec2<F>> + 'd>,
+    node_iter: Box<dyn Iterator<Item = &'d N> + 'd>,
 }
 
-impl<'d, N: Node> TangentIter<'d, N> {
-    pub fn new<T: Trajectory<Node = N>>(trajectory: &'d T) -> TangentIter<'d, N> {
+impl<'d, N: Node<F>, F: Float> TangentIter<'d, N, F> {
+    pub fn new<T: Trajectory<F, Node = N>>(trajectory: &'d T) -> TangentIter<'d, N, F> {
         let line_segment_iter = trajectory
             .iter_line_segments()
             .map(|line_segment| line_segment.end().pos() - line_segment.start().pos())
             .fuse();
+        let first_direction = trajectory.iter_line_segments().find_map(|line_segment| {
+            (line_segment.end().pos() - line_segment.start().pos()).try_normalise()
+        });
 
         TangentIter {
             line_segment_buffer: None,
+            first_direction,
             line_segment_iter: Box::new(line_segment_iter),
             node_iter: Box::new(trajectory.nodes().iter()),
         }
     }
 }
 
-impl<'d, N: Node> Iterator for TangentIter<'d, N> {
-    type Item = Line2;
-    fn next(&mut self) -> Option<Line2> {
+impl<'d, N: Node<F>, F: Float> Iterator for TangentIter<'d, N, F> {
+    type Item = Line2<F>;
+    fn next(&mut self) -> Option<Line2<F>> {
         use LineType::Line;
         let line_segment = self.line_segment_iter.next();
         let node = self.node_iter.next()?;
 
+        //line segments without a length have no direction
+        let line_segment = line_segment.and_then(|line_segment| line_segment.try_normalise());
+
         let gradient = match (self.line_segment_buffer, line_segment) {
-            (Some(first), Some(second)) => {
-                let first = first.normalise();
-                let second = second.normalise();
-                Some((first + second).normalise())
-            }
-            (None, Some(second)) => Some(second.normalise()),
-            (Some(first), None) => Some(first.normalise()),
-            (None, None) => None,
+            //if the trajectory turns around we keep the previous direction
+            (Some(first), Some(second)) => (first + second).normalise_or(first),
+            (None, Some(second)) => second,
+            (Some(first), None) => first,
+            //a leading duplicate node
+            (None, None) => self.first_direction?,
         };
 
-        self.line_segment_buffer = line_segment;
+        if line_segment.is_some() {
+            self.line_segment_buffer = line_segment;
+        }
 
         Some(Line2::from_support_point_and_vector(
             node.pos(),
-            gradient?,
+            gradient,
             Line,
         ))
     }
@@ -0,0 +1,75 @@
+use core::ops::RangeBounds;
+
+use alloc::vec::Vec;
+
+use crate::Float;
+
+use super::Trajectory;
+
+/// a trajectory whose nodes can be edited
+pub trait TrajectoryMut<F: Float = f32>: Trajectory<F> {
+    fn nodes_mut(&mut self) -> &mut [Self::Node];
+
+    /// inserts node at index, shifting all nodes after it
+    /// panics if index > number_of_nodes()
+    fn insert_node(&mut self, index: usize, node: Self::Node);
+
+    /// removes the nodes in range and returns them
+    /// panics if the range is out of bounds
+    fn remove_nodes<R: RangeBounds<usize>>(&mut self, range: R) -> Vec<Self::Node>;
+
+    /// splits the trajectory into two at the given index
+    /// self keeps the nodes [0, at) and the returned trajectory holds the nodes [at, len)
+    /// panics if at > number_of_nodes()
+    fn split_off(&mut self, at: usize) -> Self;
+
+    /// moves all nodes of other to the end of self
+    fn append(&mut self, other: &mut Self);
+
+    fn push_node(&mut self, node: Self::Node) {
+        self.insert_node(self.number_of_nodes(), node);
+    }
+
+    fn reverse(&mut self) {
+        self.nodes_mut().reverse();
+    }
+
+    fn concatenate(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+
+    /// inserts a node at the point described by (index, t) as returned by closest_point_to_pos_with_portion_and_index
+    /// node_builder gets the start and end node of the line segment and t and has to create the new node
+    /// if t is 0.0 the point is the node at index and nothing is inserted
+    /// returns the index of the node at the point
+    fn insert_at_portion(
+        &mut self,
+        index: usize,
+        t: F,
+        node_builder: impl FnOnce(&Self::Node, &Self::Node, F) -> Self::Node,
+    ) -> usize {
+        if t == F::ZERO {
+            index
+        } else {
+            let node = node_builder(&self.nodes()[index], &self.nodes()[index + 1], t);
+            self.insert_node(index + 1, node);
+            index + 1
+        }
+    }
+
+    /// splits the trajectory at the point described by (index, t) as returned by closest_point_to_pos_with_portion_and_index
+    /// the node at the point is the last node of self and the first node of the returned trajectory
+    /// see insert_at_portion for node_builder
+    fn split_at_portion(
+        &mut self,
+        index: usize,
+        t: F,
+        node_builder: impl FnOnce(&Self::Node, &Self::Node, F) -> Self::Node,
+    ) -> Self {
+        let split_index = self.insert_at_portion(index, t, node_builder);
+        let mut other = self.split_off(split_index + 1);
+        other.insert_node(0, self.nodes()[split_index].clone());
+        other
+    }
+}
@@ -0,0 +1,113 @@
+use core::ops::RangeBounds;
+
+use alloc::vec::{self, Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Float, Vec2};
+
+use super::{node::Node, Trajectory, TrajectoryMut};
+
+impl<F: Float> Node<F> for Vec2<F> {
+    fn pos(&self) -> Vec2<F> {
+        *self
+    }
+}
+
+/// a trajectory that owns its nodes
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct VecTrajectory<N> {
+    id: u64,
+    nodes: Vec<N>,
+}
+
+impl<N> VecTrajectory<N> {
+    pub fn new(id: u64) -> VecTrajectory<N> {
+        VecTrajectory::from_nodes(id, Vec::new())
+    }
+
+    pub fn from_nodes(id: u64, nodes: Vec<N>) -> VecTrajectory<N> {
+        VecTrajectory { id, nodes }
+    }
+
+    pub fn with_capacity(id: u64, capacity: usize) -> VecTrajectory<N> {
+        VecTrajectory::from_nodes(id, Vec::with_capacity(capacity))
+    }
+
+    pub fn push(&mut self, node: N) {
+        self.nodes.push(node);
+    }
+
+    pub fn into_nodes(self) -> Vec<N> {
+        self.nodes
+    }
+}
+
+impl<F: Float, N: Node<F>> Trajectory<F> for VecTrajectory<N> {
+    type Node = N;
+
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn set_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
+    fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+}
+
+impl<F: Float, N: Node<F>> TrajectoryMut<F> for VecTrajectory<N> {
+    fn nodes_mut(&mut self) -> &mut [N] {
+        &mut self.nodes
+    }
+
+    fn insert_node(&mut self, index: usize, node: N) {
+        self.nodes.insert(index, node);
+    }
+
+    fn remove_nodes<R: RangeBounds<usize>>(&mut self, range: R) -> Vec<N> {
+        self.nodes.drain(range).collect()
+    }
+
+    ///the returned trajectory has the same id as self
+    fn split_off(&mut self, at: usize) -> VecTrajectory<N> {
+        VecTrajectory::from_nodes(self.id, self.nodes.split_off(at))
+    }
+
+    fn append(&mut self, other: &mut VecTrajectory<N>) {
+        self.nodes.append(&mut other.nodes);
+    }
+}
+
+impl<N> From<Vec<N>> for VecTrajectory<N> {
+    fn from(nodes: Vec<N>) -> VecTrajectory<N> {
+        VecTrajectory::from_nodes(0, nodes)
+    }
+}
+
+///the collected trajectory has the id 0
+impl<N> FromIterator<N> for VecTrajectory<N> {
+    fn from_iter<I: IntoIterator<Item = N>>(iter: I) -> VecTrajectory<N> {
+        VecTrajectory::from_nodes(0, iter.into_iter().collect())
+    }
+}
+
+impl<N> Extend<N> for VecTrajectory<N> {
+    fn extend<I: IntoIterator<Item = N>>(&mut self, iter: I) {
+        self.nodes.extend(iter);
+    }
+}
+
+impl<N> IntoIterator for VecTrajectory<N> {
+    type Item = N;
+    type IntoIter = vec::IntoIter<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
@@ -1,35 +1,50 @@
-use std::ops::{Add, AddAssign, Div, Mul, Sub};
+use core::{
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
+#[cfg(feature = "egui")]
 use eframe::egui::{Pos2, Vec2 as GuiVec};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Default, Debug, Deserialize, Serialize)]
-pub struct Vec2 {
-    x: f32,
-    y: f32,
+use super::float::Float;
+
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Vec2<F: Float = f32> {
+    x: F,
+    y: F,
 }
 
-impl<T: Clone> From<&T> for Vec2
+impl<F: Float, T: Clone> From<&T> for Vec2<F>
 where
-    Vec2: From<T>,
+    Vec2<F>: From<T>,
 {
-    fn from(elem: &T) -> Vec2 {
+    fn from(elem: &T) -> Vec2<F> {
         Vec2::from(elem.clone())
     }
 }
 
-impl<M: Into<f32>, N: Into<f32>> From<(N, M)> for Vec2 {
-    fn from((x, y): (N, M)) -> Vec2 {
+impl<F: Float, M: Into<F>, N: Into<F>> From<(N, M)> for Vec2<F> {
+    fn from((x, y): (N, M)) -> Vec2<F> {
         Vec2::new(x.into(), y.into())
     }
 }
 
-impl From<Vec2> for (f32, f32) {
-    fn from(vec: Vec2) -> (f32, f32) {
+impl<F: Float> From<Vec2<F>> for (F, F) {
+    fn from(vec: Vec2<F>) -> (F, F) {
         (vec.x, vec.y)
     }
 }
 
+impl From<Vec2<f32>> for Vec2<f64> {
+    fn from(vec: Vec2<f32>) -> Vec2<f64> {
+        vec.cast()
+    }
+}
+
+#[cfg(feature = "egui")]
 impl From<Vec2> for Pos2 {
     fn from(vec: Vec2) -> Self {
         Pos2 {
@@ -39,6 +54,7 @@ impl From<Vec2> for Pos2 {
     }
 }
 
+#[cfg(feature = "egui")]
 impl From<&Vec2> for Pos2 {
     fn from(vec: &Vec2) -> Self {
         Pos2 {
@@ -48,18 +64,21 @@ impl From<&Vec2> for Pos2 {
     }
 }
 
+#[cfg(feature = "egui")]
 impl From<Pos2> for Vec2 {
     fn from(pos: Pos2) -> Self {
         Vec2::new(pos.x, pos.y)
     }
 }
 
+#[cfg(feature = "egui")]
 impl From<GuiVec> for Vec2 {
     fn from(vec: GuiVec) -> Self {
         Vec2::new(vec.x, vec.y)
     }
 }
 
+#[cfg(feature = "egui")]
 impl From<Vec2> for GuiVec {
     fn from(vec: Vec2) -> Self {
         GuiVec {
@@ -69,108 +88,274 @@ impl From<Vec2> for GuiVec {
     }
 }
 
-impl Vec2 {
-    pub const fn new(x: f32, y: f32) -> Vec2 {
+impl<F: Float> Vec2<F> {
+    pub const fn new(x: F, y: F) -> Vec2<F> {
         Vec2 { x, y }
     }
 
+    /// converts the vector into another precision
+    /// note: converting from f64 to f32 may lose precision
+    pub fn cast<G: Float>(self) -> Vec2<G> {
+        Vec2::new(G::from_f64(self.x.to_f64()), G::from_f64(self.y.to_f64()))
+    }
+
     pub fn is_finite(self) -> bool {
         self.x.is_finite() && self.y.is_finite()
     }
 
-    pub fn x(self) -> f32 {
+    pub fn x(self) -> F {
         self.x
     }
 
-    pub fn y(self) -> f32 {
+    pub fn y(self) -> F {
         self.y
     }
 
-    pub fn euclidean_distance(self, other: Vec2) -> f32 {
+    pub fn euclidean_distance(self, other: Vec2<F>) -> F {
         self.euclidean_distance_squared(other).sqrt()
     }
 
-    pub fn euclidean_distance_squared(self, other: Vec2) -> f32 {
+    pub fn euclidean_distance_squared(self, other: Vec2<F>) -> F {
         (other - self) * (other - self)
     }
 
-    pub fn euclidean_lenght(self) -> f32 {
+    pub fn euclidean_lenght(self) -> F {
         (self * self).sqrt()
     }
 
-    pub fn normalise(&self) -> Vec2 {
+    pub fn normalise(&self) -> Vec2<F> {
         *self / self.euclidean_lenght()
     }
 
-    pub fn get_orthogonally_vec(&self) -> Vec2 {
+    /// returns None if the vector has no length or is not finite
+    pub fn try_normalise(&self) -> Option<Vec2<F>> {
+        let normalised = self.normalise();
+        normalised.is_finite().then_some(normalised)
+    }
+
+    /// returns fallback if the vector has no length or is not finite
+    pub fn normalise_or(&self, fallback: Vec2<F>) -> Vec2<F> {
+        self.try_normalise().unwrap_or(fallback)
+    }
+
+    pub fn get_orthogonally_vec(&self) -> Vec2<F> {
         Vec2::new(self.y(), -self.x()).normalise()
     }
+
+    /// returns None if the vector has no length or is not finite
+    pub fn try_get_orthogonally_vec(&self) -> Option<Vec2<F>> {
+        Vec2::new(self.y(), -self.x()).try_normalise()
+    }
+
+    /// same as self * other
+    pub fn dot(self, other: Vec2<F>) -> F {
+        self * other
+    }
+
+    /// the z component of the 3d cross product (also called perp dot product)
+    /// positive if other is counter clockwise of self
+    pub fn cross(self, other: Vec2<F>) -> F {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn component_mul(self, other: Vec2<F>) -> Vec2<F> {
+        Vec2::new(self.x * other.x, self.y * other.y)
+    }
+
+    pub fn component_div(self, other: Vec2<F>) -> Vec2<F> {
+        Vec2::new(self.x / other.x, self.y / other.y)
+    }
+
+    /// the unsigned angle between both vectors in [0.0, PI]
+    /// returns NaN if one of the vectors has no length or is not finite
+    pub fn angle_between(self, other: Vec2<F>) -> F {
+        let cos = (self * other) / (self.euclidean_lenght() * other.euclidean_lenght());
+        if cos.is_nan() {
+            //max and min would turn NaN into a valid looking angle
+            return F::NAN;
+        }
+        cos.max(-F::ONE).min(F::ONE).acos()
+    }
+
+    /// the angle to rotate self onto other in (-PI, PI], positive for counter clockwise
+    pub fn signed_angle(self, other: Vec2<F>) -> F {
+        self.cross(other).atan2(self * other)
+    }
+
+    /// rotates counter clockwise, angle is in radians
+    pub fn rotate(self, angle: F) -> Vec2<F> {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// linear interpolation, returns self for t == 0.0 and other for t == 1.0
+    pub fn lerp(self, other: Vec2<F>, t: F) -> Vec2<F> {
+        self * (F::ONE - t) + other * t
+    }
+
+    /// the part of self that is parallel to other
+    pub fn project_onto(self, other: Vec2<F>) -> Vec2<F> {
+        other * ((self * other) / (other * other))
+    }
+
+    /// the part of self that is orthogonal to other
+    pub fn reject_from(self, other: Vec2<F>) -> Vec2<F> {
+        self - self.project_onto(other)
+    }
+
+    /// reflects self on a surface with the given normal
+    /// normal does not have to be normalised
+    pub fn reflect(self, normal: Vec2<F>) -> Vec2<F> {
+        self - self.project_onto(normal) * F::TWO
+    }
+
+    pub fn min(self, other: Vec2<F>) -> Vec2<F> {
+        Vec2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    pub fn max(self, other: Vec2<F>) -> Vec2<F> {
+        Vec2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    pub fn abs(self) -> Vec2<F> {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    /// clamps each component, min has to be <= max in each component
+    pub fn clamp(self, min: Vec2<F>, max: Vec2<F>) -> Vec2<F> {
+        self.max(min).min(max)
+    }
+
+    /// scales the vector so its length is in [min, max], keeping the direction
+    /// the zero vector is returned unchanged
+    pub fn clamp_length(self, min: F, max: F) -> Vec2<F> {
+        let length = self.euclidean_lenght();
+        if length == F::ZERO {
+            self
+        } else if length < min {
+            self * (min / length)
+        } else if length > max {
+            self * (max / length)
+        } else {
+            self
+        }
+    }
+
+    /// scales the vector so its length is at most max, keeping the direction
+    pub fn clamp_length_max(self, max: F) -> Vec2<F> {
+        self.clamp_length(F::ZERO, max)
+    }
 }
 
-impl Mul for Vec2 {
-    type Output = f32;
-    fn mul(self, rhs: Vec2) -> f32 {
+impl<F: Float> Mul for Vec2<F> {
+    type Output = F;
+    fn mul(self, rhs: Vec2<F>) -> F {
         self.x * rhs.x + self.y * rhs.y
     }
 }
 
-impl Mul<f32> for Vec2 {
-    type Output = Vec2;
-    fn mul(self, rhs: f32) -> Vec2 {
+impl<F: Float> Mul<F> for Vec2<F> {
+    type Output = Vec2<F>;
+    fn mul(self, rhs: F) -> Vec2<F> {
         Vec2::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Mul<Vec2> for f32 {
-    type Output = Vec2;
-    fn mul(self, rhs: Vec2) -> Vec2 {
-        Vec2::new(rhs.x * self, rhs.y * self)
-    }
+macro_rules! impl_scalar_mul {
+    ($float: ident) => {
+        impl Mul<Vec2<$float>> for $float {
+            type Output = Vec2<$float>;
+            fn mul(self, rhs: Vec2<$float>) -> Vec2<$float> {
+                Vec2::new(rhs.x * self, rhs.y * self)
+            }
+        }
+    };
 }
 
-impl Mul<i32> for Vec2 {
-    type Output = Vec2;
-    fn mul(self, rhs: i32) -> Vec2 {
-        Vec2::new(self.x * rhs as f32, self.y * rhs as f32)
+impl_scalar_mul!(f32);
+impl_scalar_mul!(f64);
+
+impl<F: Float> Mul<i32> for Vec2<F> {
+    type Output = Vec2<F>;
+    fn mul(self, rhs: i32) -> Vec2<F> {
+        let rhs = F::from_f64(rhs as f64);
+        Vec2::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Mul<Vec2> for i32 {
-    type Output = Vec2;
-    fn mul(self, rhs: Vec2) -> Vec2 {
-        Vec2::new(rhs.x * self as f32, rhs.y * self as f32)
+impl<F: Float> Mul<Vec2<F>> for i32 {
+    type Output = Vec2<F>;
+    fn mul(self, rhs: Vec2<F>) -> Vec2<F> {
+        rhs * self
     }
 }
 
-impl Div<f32> for Vec2 {
-    type Output = Vec2;
-    fn div(self, rhs: f32) -> Vec2 {
+impl<F: Float> Div<F> for Vec2<F> {
+    type Output = Vec2<F>;
+    fn div(self, rhs: F) -> Vec2<F> {
         Vec2::new(self.x() / rhs, self.y() / rhs)
     }
 }
 
-impl PartialEq for Vec2 {
-    fn eq(&self, other: &Vec2) -> bool {
+impl<F: Float> PartialEq for Vec2<F> {
+    fn eq(&self, other: &Vec2<F>) -> bool {
         self.x == other.x && self.y == other.y
     }
 }
 
-impl Add for Vec2 {
-    type Output = Vec2;
-    fn add(self, rhs: Vec2) -> Vec2 {
+impl<F: Float> Add for Vec2<F> {
+    type Output = Vec2<F>;
+    fn add(self, rhs: Vec2<F>) -> Vec2<F> {
         Vec2::new(self.x + rhs.x, self.y + rhs.y)
     }
 }
 
-impl Sub for Vec2 {
-    type Output = Vec2;
-    fn sub(self, rhs: Vec2) -> Vec2 {
+impl<F: Float> Sub for Vec2<F> {
+    type Output = Vec2<F>;
+    fn sub(self, rhs: Vec2<F>) -> Vec2<F> {
         self + (-1 * rhs)
     }
 }
 
-impl AddAssign for Vec2 {
+impl<F: Float> AddAssign for Vec2<F> {
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
 }
+
+impl<F: Float> SubAssign for Vec2<F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<F: Float> MulAssign<F> for Vec2<F> {
+    fn mul_assign(&mut self, rhs: F) {
+        *self = *self * rhs;
+    }
+}
+
+impl<F: Float> DivAssign<F> for Vec2<F> {
+    fn div_assign(&mut self, rhs: F) {
+        *self = *self / rhs;
+    }
+}
+
+impl<F: Float> Neg for Vec2<F> {
+    type Output = Vec2<F>;
+    fn neg(self) -> Vec2<F> {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl<F: Float> Sum for Vec2<F> {
+    fn sum<I: Iterator<Item = Vec2<F>>>(iter: I) -> Vec2<F> {
+        iter.fold(Vec2::new(F::ZERO, F::ZERO), Add::add)
+    }
+}
+
+impl<'a, F: Float> Sum<&'a Vec2<F>> for Vec2<F> {
+    fn sum<I: Iterator<Item = &'a Vec2<F>>>(iter: I) -> Vec2<F> {
+        iter.copied().sum()
+    }
+}